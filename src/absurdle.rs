@@ -0,0 +1,208 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashSet};
+
+use crate::{Hint, score, Spot};
+use crate::information::buckets;
+use crate::opening::Opening;
+use crate::ranked::{RankedHelper, Ranking};
+
+/// Number of guesses planned to the end to order suggestions.
+const PLAN_WIDTH: usize = 5;
+/// Number of guesses to give up planning after.
+const PLAN_DEPTH: usize = 20;
+
+/// Picks the hints keeping the most `candidates` alive like Absurdle does,
+/// or `None` if no candidates are remained.
+///
+/// Ties are broken by fewer letters at the spot, then fewer letters somewhere.
+pub fn adversarial_feedback(guess: &str, candidates: &[String]) -> Option<Vec<Hint>> {
+    let mut buckets: BTreeMap<String, (usize, Vec<Hint>)> = BTreeMap::new();
+    for candidate in candidates {
+        let hints = score(guess, candidate);
+        buckets.entry(Hint::pattern(&hints)).or_insert((0, hints)).0 += 1;
+    }
+    buckets.into_values()
+        .max_by_key(|(count, hints)| {
+            let at = hints.iter().filter(|h| matches!(h.spot, Spot::At(_))).count();
+            let somewhere = hints.iter().filter(|h| matches!(h.spot, Spot::InWithout(_))).count();
+            (*count, Reverse(at), Reverse(somewhere))
+        })
+        .map(|(_, hints)| hints)
+}
+
+/// Orders `guess_words` by the largest bucket of `answers` the adversary can keep, answers first on ties.
+fn rank_worst_case(guess_words: &[String], answers: &[String]) -> Vec<String> {
+    let answer_chars: Vec<Vec<char>> = answers.iter()
+        .map(|word| {
+            word.chars().collect()
+        })
+        .collect();
+    let candidates: HashSet<&String> = answers.iter().collect();
+    let mut scored: Vec<(&String, usize, bool)> = guess_words.iter()
+        .map(|word| {
+            let chars: Vec<char> = word.chars().collect();
+            let worst = buckets(&chars, &answer_chars).values().max().copied().unwrap_or(0);
+            (word, worst, candidates.contains(word))
+        })
+        .collect();
+    scored.sort_by(|a, b| {
+        a.1.cmp(&b.1).then(b.2.cmp(&a.2))
+    });
+    scored.into_iter()
+        .map(|(word, _, _)| {
+            word.to_string()
+        })
+        .collect()
+}
+
+/// Counts guesses to win against the adversary from `guess`, following the
+/// smallest worst case afterwards.
+fn plan(guess: &str, answers: &[String], guess_words: &[String]) -> usize {
+    let mut answers = answers.to_vec();
+    let mut guess = guess.to_string();
+    for count in 1..=PLAN_DEPTH {
+        let hints = match adversarial_feedback(&guess, &answers) {
+            Some(hints) => hints,
+            None => break,
+        };
+        if Hint::all_at(&hints) {
+            return count;
+        }
+        answers.retain(|answer| score(&guess, answer) == hints);
+        guess = match rank_worst_case(guess_words, &answers).into_iter().next() {
+            Some(word) => word,
+            None => break,
+        };
+    }
+    PLAN_DEPTH + 1
+}
+
+/// Orders the few guesses with the smallest worst case by the number of
+/// guesses to win against the adversary.
+#[derive(Debug)]
+pub struct Adversary;
+
+impl Ranking for Adversary {
+    fn rank(&self, guess_words: &[String], answers: &[String]) -> Vec<(String, f64)> {
+        let mut planned: Vec<(String, usize)> = rank_worst_case(guess_words, answers).into_iter()
+            .take(PLAN_WIDTH)
+            .map(|word| {
+                let count = plan(&word, answers, guess_words);
+                (word, count)
+            })
+            .collect();
+        planned.sort_by_key(|(_, count)| *count);
+        planned.into_iter()
+            .map(|(word, count)| {
+                (word, count as f64)
+            })
+            .collect()
+    }
+}
+
+/// A helper against an adversary like Absurdle, which suggests guesses
+/// planned to win in the fewest guesses.
+///
+/// Only a few guesses with the smallest worst case are planned, so it
+/// suggests them alone with the number of guesses to win as scores.
+pub type AbsurdleHelper = RankedHelper<Adversary>;
+
+impl AbsurdleHelper {
+    pub fn new(width: usize, dict_words: &[String]) -> AbsurdleHelper {
+        Self::with_guesses(width, dict_words, &[])
+    }
+
+    pub fn with_guesses(width: usize, answer_words: &[String], guess_words: &[String]) -> AbsurdleHelper {
+        Self::ranked(width, answer_words, guess_words, Adversary)
+    }
+
+    pub fn with_opening(width: usize, answer_words: &[String], guess_words: &[String], opening: Opening) -> AbsurdleHelper {
+        Self::opened(width, answer_words, guess_words, Adversary, opening)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{check_hard_mode, Helper};
+
+    fn preset_words() -> Vec<String> {
+        vec![
+            "hello".to_string(),
+            "early".to_string(),
+            "asset".to_string(),
+            "bound".to_string(),
+            "beard".to_string(),
+            "bears".to_string(),
+        ]
+    }
+
+    #[cfg(test)]
+    mod adversarial_feedback {
+        use super::*;
+
+        #[test]
+        fn largest_bucket() {
+            // "beard" and "bears" give the same hints to "hello".
+            let actual = adversarial_feedback("hello", &preset_words()).unwrap();
+            assert_eq!(actual, score("hello", "bears"));
+        }
+
+        #[test]
+        fn fewer_at_on_ties() {
+            let actual = adversarial_feedback("beard", &["bears".to_string(), "hello".to_string()]).unwrap();
+            assert_eq!(actual, score("beard", "hello"));
+        }
+
+        #[test]
+        fn solved_only_when_one_remained() {
+            let actual = adversarial_feedback("early", &["early".to_string()]).unwrap();
+            assert!(Hint::all_at(&actual));
+        }
+
+        #[test]
+        fn empty() {
+            assert_eq!(adversarial_feedback("early", &[]), None);
+        }
+    }
+
+    #[test]
+    fn plan_to_win() {
+        assert_eq!(plan("early", &["early".to_string()], &preset_words()), 1);
+        assert_eq!(plan("hello", &preset_words(), &preset_words()), 3);
+    }
+
+    #[cfg(test)]
+    mod suggest {
+        use super::*;
+
+        #[test]
+        fn sorted_by_guesses_to_win() {
+            let target = AbsurdleHelper::new(5, &preset_words());
+            let scores = target.scores().unwrap();
+            assert_eq!(target.suggest().len(), PLAN_WIDTH);
+            assert!(scores.windows(2).all(|w| w[0] <= w[1]));
+        }
+
+        #[test]
+        fn hard_mode() {
+            let mut target = AbsurdleHelper::new(5, &preset_words()).with_hard_mode(true);
+            let hints = adversarial_feedback("hello", &preset_words()).unwrap();
+            target.add_hint("hello", &hints);
+            assert!(!target.suggest().is_empty());
+            assert!(target.suggest().iter().all(|word| check_hard_mode(word, &hints).is_ok()));
+        }
+
+        #[test]
+        fn rebuild() {
+            let mut target = AbsurdleHelper::new(5, &preset_words());
+            let initial = target.suggest().clone();
+            let hints = adversarial_feedback("hello", &preset_words()).unwrap();
+            target.add_hint("hello", &hints);
+            assert_eq!(target.remained_words_length(), 2);
+            assert_eq!(target.scores().unwrap()[0], 2.0);
+            target.rebuild(&[]);
+            assert_eq!(target.suggest(), &initial);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::BTreeMap;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rand::seq::index;
+use serde::Serialize;
+
+use crate::{Hint, Helper, score};
+
+/// A game played by a helper against a known answer.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct GameResult {
+    pub answer: String,
+    pub guesses: Vec<String>,
+    pub solved: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BenchReport {
+    pub games: usize,
+    pub solved: usize,
+    pub failed: usize,
+    pub failure_rate: f64,
+    /// Average number of guesses of solved games.
+    pub average_guesses: f64,
+    /// Number of solved games by number of guesses.
+    pub histogram: BTreeMap<usize, usize>,
+    /// Games sorted from the most guesses, failures first.
+    pub worst: Vec<GameResult>,
+}
+
+/// Plays one game by always guessing the first suggestion of `helper`.
+///
+/// The helper is reset before the game, so the same helper can play many games.
+pub fn play(helper: &mut dyn Helper, answer: &str, max_guess_count: usize) -> GameResult {
+    helper.reset();
+    let mut guesses: Vec<String> = Vec::new();
+    while guesses.len() < max_guess_count {
+        let guess = match helper.suggest().first() {
+            Some(word) => word.to_string(),
+            None => break,
+        };
+        let hints = score(&guess, answer);
+        guesses.push(guess.to_string());
+        if Hint::all_at(&hints) {
+            return GameResult { answer: answer.to_string(), guesses, solved: true };
+        }
+        helper.add_hint(&guess, &hints);
+    }
+    GameResult { answer: answer.to_string(), guesses, solved: false }
+}
+
+/// Plays every word of `answers` and summarizes the results with `worst_count` worst games.
+pub fn bench(helper: &mut dyn Helper, answers: &[String], max_guess_count: usize, worst_count: usize) -> BenchReport {
+    let mut results: Vec<GameResult> = answers.iter()
+        .map(|answer| {
+            play(helper, answer, max_guess_count)
+        })
+        .collect();
+
+    let mut histogram: BTreeMap<usize, usize> = BTreeMap::new();
+    let mut total_guesses = 0;
+    for result in results.iter().filter(|r| r.solved) {
+        *histogram.entry(result.guesses.len()).or_insert(0) += 1;
+        total_guesses += result.guesses.len();
+    }
+    let solved = histogram.values().sum();
+    let failed = results.len() - solved;
+
+    results.sort_by(|a, b| {
+        a.solved.cmp(&b.solved).then(b.guesses.len().cmp(&a.guesses.len()))
+    });
+    results.truncate(worst_count);
+
+    BenchReport {
+        games: answers.len(),
+        solved,
+        failed,
+        failure_rate: if answers.is_empty() { 0.0 } else { failed as f64 / answers.len() as f64 },
+        average_guesses: if solved == 0 { 0.0 } else { total_guesses as f64 / solved as f64 },
+        histogram,
+        worst: results,
+    }
+}
+
+/// Picks `count` words at random with `seed`, keeping the order of `words`.
+pub fn sample(words: &[String], count: usize, seed: u64) -> Vec<String> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut indexes = index::sample(&mut rng, words.len(), count.min(words.len())).into_vec();
+    indexes.sort_unstable();
+    indexes.iter()
+        .map(|i| {
+            words[*i].to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simple::SimpleHelper;
+
+    fn preset_words() -> Vec<String> {
+        vec![
+            "hello".to_string(),
+            "early".to_string(),
+            "asset".to_string(),
+            "bound".to_string(),
+            "heard".to_string(),
+        ]
+    }
+
+    #[cfg(test)]
+    mod play {
+        use super::*;
+
+        #[test]
+        fn solved() {
+            let mut helper = SimpleHelper::new(5, &preset_words());
+            let actual = play(&mut helper, "asset", 6);
+            assert_eq!(actual, GameResult {
+                answer: String::from("asset"),
+                guesses: vec![String::from("hello"), String::from("asset")],
+                solved: true,
+            });
+        }
+
+        #[test]
+        fn failed() {
+            let mut helper = SimpleHelper::new(5, &preset_words());
+            let actual = play(&mut helper, "heard", 1);
+            assert!(!actual.solved);
+            assert_eq!(actual.guesses, vec![String::from("hello")]);
+        }
+
+        #[test]
+        fn not_in_dictionary() {
+            let mut helper = SimpleHelper::new(5, &preset_words());
+            let actual = play(&mut helper, "zzzzz", 6);
+            assert!(!actual.solved);
+            assert_eq!(actual.guesses.len(), 1);
+        }
+    }
+
+    #[test]
+    fn bench_all() {
+        let mut helper = SimpleHelper::new(5, &preset_words());
+        let mut answers = preset_words();
+        answers.push("zzzzz".to_string());
+        let actual = bench(&mut helper, &answers, 6, 2);
+        assert_eq!(actual.games, 6);
+        assert_eq!(actual.solved, 5);
+        assert_eq!(actual.failed, 1);
+        assert!((actual.failure_rate - 1.0 / 6.0).abs() < 1e-9);
+        assert_eq!(actual.histogram, BTreeMap::from([(1, 1), (2, 4)]));
+        assert!((actual.average_guesses - 1.8).abs() < 1e-9);
+        assert_eq!(actual.worst.len(), 2);
+        assert_eq!(actual.worst[0].answer, "zzzzz");
+        assert!(!actual.worst[0].solved);
+        assert_eq!(actual.worst[1].guesses.len(), 2);
+    }
+
+    #[test]
+    fn sample_keeps_order() {
+        let actual = sample(&preset_words(), 3, 1);
+        assert_eq!(actual.len(), 3);
+        assert_eq!(actual, sample(&preset_words(), 3, 1));
+        let positions: Vec<usize> = actual.iter()
+            .map(|w| {
+                preset_words().iter().position(|p| p == w).unwrap()
+            })
+            .collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]));
+    }
+}
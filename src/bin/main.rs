@@ -1,55 +1,934 @@
+use std::io::{Read, Write};
 use std::iter::zip;
+use std::path::Path;
 
 use ansi_term::{ANSIGenericString, Colour, Style};
-use ansi_term::Color::{RGB, White};
+use ansi_term::Color::{RGB, White, Yellow};
 use ansi_term::Colour::Cyan;
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
 use dialoguer::{Confirm, FuzzySelect, Input, Select};
 use dialoguer::theme::ColorfulTheme;
 use num_format::{Locale, ToFormattedString};
+use serde::Serialize;
 
-use wordle_helper::{Dictionary, Hint, Helper, Spot};
+use wordle_helper::{check_hard_mode, Dictionary, Hint, Helper, score, Spot, Weights};
+use wordle_helper::absurdle::AbsurdleHelper;
+use wordle_helper::bench;
+use wordle_helper::bench::BenchReport;
+use wordle_helper::diagnosis::Diagnosis;
+use wordle_helper::error::Error;
+use wordle_helper::fibble::{FibbleHelper, FibbleInformationHelper};
+use wordle_helper::game::{Game, Pick, pick_answer};
+use wordle_helper::information::InformationHelper;
+use wordle_helper::minimax::{Criterion, MinimaxHelper};
+use wordle_helper::multi::MultiHelper;
+use wordle_helper::opening;
+use wordle_helper::opening::Opening;
+use wordle_helper::session::Session;
+use wordle_helper::share;
+use wordle_helper::share::Tiles;
 use wordle_helper::simple::SimpleHelper;
-use wordle_helper::txt::TxtDictionary;
+use wordle_helper::tree;
+use wordle_helper::tree::{Tree, TreeHelper};
+use wordle_helper::txt::{DownloadPolicy, Progress, TxtLoader};
 
-#[derive(Parser)]
+#[derive(Parser, Clone)]
 #[clap(version, about, long_about = None)]
 struct Config {
-    #[clap(short, long, default_value_t = 5, help = "length of one word")]
+    #[clap(short, long, global = true, default_value_t = 5, help = "length of one word")]
     word_length: usize,
 
-    #[clap(short, long, default_value_t = 6, help = "number of answer you can guess")]
+    #[clap(short, long, global = true, default_value_t = 6, help = "number of answer you can guess")]
     max_guess_count: usize,
 
-    #[clap(short, long, hide_default_value = true, default_value = "", help = "dictionary path")]
+    #[clap(short, long, global = true, visible_alias = "answers", hide_default_value = true, default_value = "", help = "dictionary path of possible answers")]
     dict_path: String,
+
+    #[clap(short, long, global = true, help = "dictionary path of words allowed to guess in addition to answers")]
+    guesses: Option<String>,
+
+    #[clap(long, global = true, help = "download the default dictionary without asking if it is not found")]
+    download: bool,
+
+    #[clap(short, long, global = true, value_enum, default_value_t = Strategy::Simple, help = "how to order suggestions")]
+    strategy: Strategy,
+
+    #[clap(long, global = true, help = "reject guesses which do not use revealed hints")]
+    hard_mode: bool,
+
+    #[clap(long, global = true, conflicts_with_all = &["strategy", "hard-mode", "lies"], help = "path of decision tree exported as JSON to walk instead of the strategy")]
+    tree: Option<String>,
+
+    #[clap(long, global = true, default_value_t = 0, conflicts_with_all = &["hard-mode", "tree"], help = "number of tiles per row which may lie like 1 for Fibble")]
+    lies: usize,
+
+    #[clap(long, conflicts_with = "from-share", help = "resume the last interrupted game")]
+    resume: bool,
+
+    #[clap(long, global = true, help = "path of share text to import rounds from, - for stdin")]
+    from_share: Option<String>,
+
+    #[clap(long, global = true, use_value_delimiter = true, help = "guessed words of share text separated by comma")]
+    share_words: Vec<String>,
+
+    #[clap(long, value_enum, help = "export the game when it ends")]
+    export: Option<Export>,
+
+    #[clap(long, help = "file path to write the export to instead of printing")]
+    export_path: Option<String>,
+
+    #[clap(long, default_value = "Wordle", help = "puzzle label of exported share text")]
+    share_label: String,
+
+    #[clap(long, help = "use high contrast tiles in exported share text")]
+    high_contrast: bool,
+
+    #[clap(long, default_value_t = 1, conflicts_with_all = &["resume", "from-share", "tree", "export", "lies", "strategy", "hard-mode"], help = "number of boards sharing each guess like 2 for Dordle, 4 for Quordle and 8 for Octordle")]
+    boards: usize,
+
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Clone)]
+enum Command {
+    #[clap(about = "Print remained words for guesses and hints without prompts")]
+    Solve {
+        #[clap(help = "guess and hint pairs like crane:01020")]
+        rounds: Vec<String>,
+
+        #[clap(short = 'n', long, default_value_t = 0, help = "number of ranked suggestions to print")]
+        top: usize,
+
+        #[clap(short, long, value_enum, default_value_t = Format::Text, help = "output format")]
+        format: Format,
+    },
+    #[clap(about = "Play a game hosted locally with an answer picked from the dictionary")]
+    Play {
+        #[clap(long, help = "seed to pick the answer")]
+        seed: Option<u64>,
+
+        #[clap(long, conflicts_with = "seed", help = "index of the answer in the dictionary like a daily puzzle number")]
+        daily: Option<usize>,
+
+        #[clap(long, help = "show remained words and suggestions every round")]
+        assist: bool,
+
+        #[clap(long, conflicts_with_all = &["seed", "daily"], help = "let the host dodge guesses like Absurdle instead of picking an answer")]
+        absurdle: bool,
+    },
+    #[clap(about = "Build a decision tree of guesses with the strategy for every answer")]
+    Tree {
+        #[clap(short, long, value_enum, default_value_t = Format::Text, help = "output format")]
+        format: Format,
+
+        #[clap(short, long, help = "file path to write the tree to instead of printing")]
+        output: Option<String>,
+    },
+    #[clap(about = "Play every answer with the strategy and report how many guesses it takes")]
+    Bench {
+        #[clap(long, help = "number of answers to sample at random instead of all answers")]
+        sample: Option<usize>,
+
+        #[clap(long, default_value_t = 0, help = "seed to sample answers")]
+        seed: u64,
+
+        #[clap(long, default_value_t = 10, help = "number of worst games to print")]
+        worst: usize,
+
+        #[clap(short, long, value_enum, default_value_t = Format::Text, help = "output format")]
+        format: Format,
+    },
+}
+
+#[derive(Clone, ValueEnum)]
+enum Export {
+    Share,
+    Transcript,
+}
+
+#[derive(Clone, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+const GUESS_SUGGESTIONS: &str = "Use suggestions";
+const GUESS_MANUALLY: &str = "Input manually";
+const UNDO_ROUND: &str = "Undo last round";
+const EDIT_ROUND: &str = "Edit a round";
+
+const DIAGNOSIS_NEAR_MISSES: usize = 10;
+
+const EXIT_SOLVED: i32 = 0;
+const EXIT_ERROR: i32 = 1;
+const EXIT_REMAINED: i32 = 3;
+const EXIT_NO_MATCH: i32 = 4;
+const EXIT_LOST: i32 = 5;
+
+#[derive(Clone, ValueEnum)]
+enum Strategy {
+    Simple,
+    Entropy,
+    Minimax,
+    ExpectedSize,
+    Absurdle,
+}
+
+impl Strategy {
+    fn score_unit(&self) -> &'static str {
+        match self {
+            Strategy::Simple => "",
+            Strategy::Entropy => "bits",
+            Strategy::Minimax => "words at most",
+            Strategy::ExpectedSize => "words on average",
+            Strategy::Absurdle => "guesses to win",
+        }
+    }
 }
 
 fn main() {
     let config = Config::parse();
+    if config.boards > 1 && config.command.is_some() {
+        eprintln!("--boards is supported only without subcommands");
+        std::process::exit(EXIT_ERROR);
+    }
 
-    println!("{}", Style::new().bold().paint("Welcome to WORDLE HELPER"));
+    match &config.command {
+        Some(Command::Solve { rounds, top, format }) => solve(&config, rounds, *top, format),
+        Some(Command::Play { seed, daily, assist, absurdle }) => {
+            let pick = match (seed, daily) {
+                (Some(seed), _) => Pick::Seed(*seed),
+                (_, Some(daily)) => Pick::Daily(*daily),
+                _ => Pick::Random,
+            };
+            play(&config, pick, *assist, *absurdle)
+        }
+        Some(Command::Tree { format, output }) => run_tree(&config, format, output),
+        Some(Command::Bench { sample, seed, worst, format }) => run_bench(&config, *sample, *seed, *worst, format),
+        None if config.boards > 1 => interactive_boards(&config),
+        None => interactive(&config),
+    }
+}
 
-    let dictionary: Box<dyn Dictionary> = Box::new(match TxtDictionary::new(&config.dict_path) {
+/// Loads the dictionary, asking before downloading the default one only if `ask`,
+/// or exits with the reason.
+fn load_dictionary(config: &Config, ask: bool) -> Box<dyn Dictionary> {
+    let download = match (config.download, ask) {
+        (true, _) => DownloadPolicy::Always,
+        (false, true) => DownloadPolicy::Ask(Box::new(confirm_download)),
+        (false, false) => DownloadPolicy::Never,
+    };
+    let mut loader = TxtLoader::new()
+        .with_download(download)
+        .with_progress(print_progress);
+    if !config.dict_path.is_empty() {
+        loader = loader.with_path(&config.dict_path);
+    }
+    if let Some(path) = &config.guesses {
+        loader = loader.with_guesses(path);
+    }
+    Box::new(match loader.load() {
         Ok(d) => d,
         Err(e) => {
-            println!();
+            eprintln!();
             eprintln!("failed to load dictionary: {}", e);
-            std::process::exit(1);
+            if matches!(e, Error::NotFound(_)) && config.dict_path.is_empty() {
+                eprintln!("give --download to download it");
+            }
+            std::process::exit(EXIT_ERROR);
+        }
+    })
+}
+
+fn confirm_download(path: &Path) -> bool {
+    eprintln!();
+    eprintln!("{}",
+             Style::new().fg(Yellow).paint(
+                 format!("Default dictionary is not found at {}", path.to_str().unwrap())
+             )
+    );
+    eprintln!("{}",
+             Style::new().fg(Yellow).paint(
+                 "So I should download dictionary from https://github.com/dwyl/english-words (about 4.04MB)"
+             )
+    );
+    Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("Do you want to continue?")
+        .interact()
+        .unwrap_or(false)
+}
+
+fn print_progress(progress: Progress) {
+    match progress {
+        Progress::Started { url, .. } => eprintln!("{}", Style::new().fg(Yellow).paint(format!("Downloading {}", url))),
+        Progress::Received { bytes, total: Some(total) } => {
+            eprint!("\r{}", Style::new().fg(Yellow).paint(format!("{} / {} bytes", bytes, total)));
+            std::io::stderr().flush().unwrap();
         }
+        Progress::Received { .. } => {}
+        Progress::Finished => {
+            eprintln!();
+            eprintln!("{}", Style::new().fg(Yellow).paint("Complete!"));
+        }
+    }
+}
+
+/// Extracts answers, guesses and weights of the word length, or exits with the reason.
+fn extract_words(config: &Config, dictionary: &dyn Dictionary) -> (Vec<String>, Vec<String>, Weights) {
+    let words = dictionary.extract_words(config.word_length).and_then(|answers| {
+        Ok((answers, dictionary.extract_guesses(config.word_length)?, dictionary.extract_weights(config.word_length)?))
     });
+    match words {
+        Ok(words) => words,
+        Err(e) => {
+            eprintln!("failed to load dictionary: {}", e);
+            std::process::exit(EXIT_ERROR);
+        }
+    }
+}
+
+fn new_helper(config: &Config, dict_words: &[String], guess_words: &[String], weights: &Weights) -> Box<dyn Helper> {
+    if let Some(path) = &config.tree {
+        return Box::new(TreeHelper::new(config.word_length, dict_words, load_tree(config, path, dict_words).root));
+    }
+    if config.lies > 0 {
+        if !weights.is_empty() {
+            eprintln!("--lies does not support dictionaries of weights");
+            std::process::exit(EXIT_ERROR);
+        }
+        return match config.strategy {
+            Strategy::Simple => Box::new(FibbleHelper::new(config.word_length, dict_words, config.lies)),
+            Strategy::Entropy => Box::new(FibbleInformationHelper::with_guesses(config.word_length, dict_words, guess_words, config.lies)),
+            _ => {
+                eprintln!("--lies supports only simple and entropy strategies");
+                std::process::exit(EXIT_ERROR);
+            }
+        };
+    }
+    let strategy = config.strategy.to_possible_value().unwrap().get_name();
+    if !weights.is_empty() && !matches!(config.strategy, Strategy::Entropy) {
+        // Other strategies count answers as if they were all as likely.
+        eprintln!("{} strategy does not support dictionaries of weights", strategy);
+        std::process::exit(EXIT_ERROR);
+    }
+    let hash = opening::dictionary_hash(dict_words, guess_words, weights);
+    let opening_path = Opening::default_path(config.word_length, strategy, &hash);
+    let opening = match config.strategy {
+        Strategy::Simple => None,
+        _ => Opening::load(&opening_path, config.word_length, strategy, &hash).unwrap_or(None),
+    };
+    let cached = opening.is_some();
+    let helper: Box<dyn Helper> = match config.strategy {
+        Strategy::Simple => Box::new(SimpleHelper::new(config.word_length, dict_words)),
+        Strategy::Entropy => Box::new(match opening {
+            Some(opening) => InformationHelper::with_opening(config.word_length, dict_words, guess_words, weights.clone(), opening),
+            None => InformationHelper::with_weights(config.word_length, dict_words, guess_words, weights.clone()),
+        }.with_hard_mode(config.hard_mode)),
+        Strategy::Minimax => Box::new(match opening {
+            Some(opening) => MinimaxHelper::with_opening(config.word_length, dict_words, guess_words, Criterion::WorstCase, opening),
+            None => MinimaxHelper::with_guesses(config.word_length, dict_words, guess_words, Criterion::WorstCase),
+        }.with_hard_mode(config.hard_mode)),
+        Strategy::ExpectedSize => Box::new(match opening {
+            Some(opening) => MinimaxHelper::with_opening(config.word_length, dict_words, guess_words, Criterion::ExpectedSize, opening),
+            None => MinimaxHelper::with_guesses(config.word_length, dict_words, guess_words, Criterion::ExpectedSize),
+        }.with_hard_mode(config.hard_mode)),
+        Strategy::Absurdle => Box::new(match opening {
+            Some(opening) => AbsurdleHelper::with_opening(config.word_length, dict_words, guess_words, opening),
+            None => AbsurdleHelper::with_guesses(config.word_length, dict_words, guess_words),
+        }.with_hard_mode(config.hard_mode)),
+    };
+    if let (false, Some(scores)) = (cached, helper.scores()) {
+        // The cache only saves time, so failing to write it is not worth stopping for.
+        let _ = Opening::new(config.word_length, strategy, &hash, helper.suggest(), scores).save(&opening_path);
+    }
+    helper
+}
+
+fn parse_round(word_length: usize, round: &str) -> Result<InputState, &'static str> {
+    let (word, hint) = match round.rsplit_once(':') {
+        Some(pair) => pair,
+        None => return Err("round must be like crane:01020"),
+    };
+    build_state(word_length, word, hint)
+}
+
+fn build_state(word_length: usize, word: &str, hint: &str) -> Result<InputState, &'static str> {
+    let mut state = InputState::new(word_length);
+    state.add_word(word)?;
+    state.add_hint(hint)?;
+    Ok(state)
+}
+
+fn read_share_text(path: &str) -> Result<String, std::io::Error> {
+    if path == "-" {
+        let mut text = String::new();
+        std::io::stdin().read_to_string(&mut text)?;
+        return Ok(text);
+    }
+    std::fs::read_to_string(path)
+}
+
+/// Imports rounds from share text, asking guessed words of each row if `ask_words` and they are not given.
+fn load_share_rounds(config: &Config, path: &str, ask_words: bool) -> Vec<(String, Vec<Hint>)> {
+    let text = match read_share_text(path) {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!("failed to read share text: {}", e);
+            std::process::exit(EXIT_ERROR);
+        }
+    };
+    let patterns = match share::parse_patterns(&text) {
+        Ok(patterns) => patterns,
+        Err(e) => {
+            eprintln!("failed to parse share text: {}", e);
+            std::process::exit(EXIT_ERROR);
+        }
+    };
+    if config.share_words.is_empty() && !ask_words {
+        eprintln!("--share-words is required to import share text");
+        std::process::exit(EXIT_ERROR);
+    }
+    let words: Vec<String> = if !config.share_words.is_empty() {
+        config.share_words.clone()
+    } else {
+        patterns.iter()
+            .map(|pattern| {
+                Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt(format!("Guess of {}", colorize_pattern(pattern)))
+                    .validate_with({
+                        let pattern = pattern.to_string();
+                        move |input: &String| -> Result<(), &str> {
+                            Hint::from_pattern(input.trim(), &pattern).map(|_| ())
+                        }
+                    })
+                    .interact_text()
+                    .unwrap()
+                    .trim()
+                    .to_string()
+            })
+            .collect()
+    };
+    match share::parse_share(&text, &words) {
+        Ok(hints) => zip(words, hints).collect(),
+        Err(e) => {
+            eprintln!("failed to import share text: {}", e);
+            std::process::exit(EXIT_ERROR);
+        }
+    }
+}
+
+fn solve(config: &Config, rounds: &[String], top: usize, format: &Format) {
+    let mut states: Vec<InputState> = Vec::new();
+    if let Some(path) = &config.from_share {
+        for (word, hints) in load_share_rounds(config, path, false) {
+            states.push(build_state(config.word_length, &word, &Hint::pattern(&hints)).unwrap_or_else(|e| {
+                eprintln!("failed to import \"{}\": {}", word, e);
+                std::process::exit(EXIT_ERROR);
+            }));
+        }
+    }
+    for round in rounds {
+        match parse_round(config.word_length, round) {
+            Ok(state) => states.push(state),
+            Err(e) => {
+                eprintln!("failed to parse \"{}\": {}", round, e);
+                std::process::exit(EXIT_ERROR);
+            }
+        }
+    }
+    if config.hard_mode {
+        for (i, state) in states.iter().enumerate() {
+            for previous in &states[..i] {
+                if let Err(e) = check_hard_mode(state.word().unwrap(), &previous.hint) {
+                    eprintln!("\"{}\" is not allowed in hard mode: {}", state.word().unwrap(), e);
+                    std::process::exit(EXIT_ERROR);
+                }
+            }
+        }
+    }
+
+    let (dict_words, guess_words, weights) = extract_words(config, load_dictionary(config, false).as_ref());
+    let mut helper = new_helper(config, &dict_words, &guess_words, &weights);
+    let mut solved_word: Option<&str> = None;
+    for state in &states {
+        let (word, hints) = state.get().unwrap();
+        if Hint::all_at(hints) {
+            solved_word = Some(word);
+            break;
+        }
+        helper.add_hint(word, hints);
+    }
+    let report = SolveReport::new(config.word_length, &states, helper.as_ref(), top, solved_word);
+
+    match format {
+        Format::Text => println!("{}", report.text()),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&report).unwrap()),
+    }
+    if report.remained_count == 0 && solved_word.is_none() {
+        let rounds: Vec<(String, Vec<Hint>)> = states.iter()
+            .map(|state| {
+                let (word, hints) = state.get().unwrap();
+                (word.to_string(), hints.clone())
+            })
+            .collect();
+        eprintln!("{}", Diagnosis::new(&dict_words, &rounds).text(DIAGNOSIS_NEAR_MISSES));
+    }
+
+    std::process::exit(match report.remained_count {
+        0 => EXIT_NO_MATCH,
+        1 => EXIT_SOLVED,
+        _ => EXIT_REMAINED,
+    });
+}
+
+/// Machine-readable result of `solve`, printed with `--format json`.
+///
+/// Fields are only added, never renamed or removed, within the same `version`.
+#[derive(Debug, Serialize)]
+struct SolveReport {
+    version: u32,
+    word_length: usize,
+    rounds: Vec<RoundReport>,
+    solved: bool,
+    remained_count: usize,
+    remained_words: Vec<String>,
+    suggestions: Vec<SuggestionReport>,
+}
+
+#[derive(Debug, Serialize)]
+struct RoundReport {
+    guess: String,
+    pattern: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SuggestionReport {
+    word: String,
+    score: Option<f64>,
+    /// Only with a dictionary of weights.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    probability: Option<f64>,
+}
+
+impl SolveReport {
+    const VERSION: u32 = 1;
+
+    fn new(word_length: usize, states: &[InputState], helper: &dyn Helper, top: usize, solved_word: Option<&str>) -> Self {
+        let rounds = states.iter()
+            .map(|state| {
+                let (word, hints) = state.get().unwrap();
+                RoundReport { guess: word.to_string(), pattern: Hint::pattern(hints) }
+            })
+            .collect();
+        let remained_words = match solved_word {
+            Some(word) => vec![word.to_string()],
+            None => helper.remained_words().clone(),
+        };
+        let suggestions = match solved_word {
+            Some(_) => Vec::new(),
+            None => helper.suggest().iter()
+                .enumerate()
+                .take(top)
+                .map(|(i, word)| {
+                    SuggestionReport {
+                        word: word.to_string(),
+                        score: helper.scores().map(|scores| scores[i]),
+                        probability: helper.probability(word),
+                    }
+                })
+                .collect(),
+        };
+        SolveReport {
+            version: Self::VERSION,
+            word_length,
+            rounds,
+            solved: remained_words.len() == 1,
+            remained_count: remained_words.len(),
+            remained_words,
+            suggestions,
+        }
+    }
+
+    fn text(&self) -> String {
+        let mut lines: Vec<String> = self.remained_words.clone();
+        if !self.suggestions.is_empty() {
+            lines.push(String::new());
+            for suggestion in &self.suggestions {
+                match (suggestion.score, suggestion.probability) {
+                    (Some(score), Some(probability)) => lines.push(format!("{} {:.4} {:.4}", suggestion.word, score, probability)),
+                    (Some(score), None) => lines.push(format!("{} {:.4}", suggestion.word, score)),
+                    (None, _) => lines.push(suggestion.word.to_string()),
+                }
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+fn load_session(config: &mut Config, session_path: &Path) -> Session {
+    if !config.resume {
+        if session_path.exists() {
+            println!("{}", Cyan.paint("There is an interrupted game. Run with --resume to continue it."));
+        }
+        return Session::new(config.word_length, config.max_guess_count, &config.dict_path, config.guesses.as_deref());
+    }
+    match Session::load(session_path) {
+        Ok(Some(session)) => {
+            config.word_length = session.word_length;
+            config.max_guess_count = session.max_guess_count;
+            config.dict_path = session.dict_path.to_string();
+            config.guesses = session.guesses_path.clone();
+            session
+        }
+        Ok(None) => {
+            eprintln!("there is no interrupted game to resume");
+            std::process::exit(EXIT_ERROR);
+        }
+        Err(e) => {
+            eprintln!("failed to load session: {}", e);
+            std::process::exit(EXIT_ERROR);
+        }
+    }
+}
+
+/// Rebuilds the helper and the session from the rounds left in `states`.
+fn replay(helper: &mut dyn Helper, session: &mut Session, states: &InputStates) {
+    let rounds = states.rounds();
+    helper.rebuild(&rounds);
+    session.rounds.clear();
+    for (word, hints) in &rounds {
+        session.add_round(word, &Hint::pattern(hints));
+    }
+}
+
+fn save_session(session: &Session, session_path: &Path) {
+    if let Err(e) = session.save(session_path) {
+        eprintln!("failed to save session: {}", e);
+    }
+}
+
+fn finish_session(session_path: &Path) {
+    if let Err(e) = Session::remove(session_path) {
+        eprintln!("failed to remove session: {}", e);
+    }
+}
+
+fn export_game(config: &Config, rounds: &[(String, Vec<Hint>)]) {
+    let text = match config.export {
+        Some(Export::Share) => {
+            let hints: Vec<Vec<Hint>> = rounds.iter()
+                .map(|(_, hints)| {
+                    hints.clone()
+                })
+                .collect();
+            let tiles = if config.high_contrast { Tiles::HighContrast } else { Tiles::Normal };
+            share::to_share_text(&config.share_label, &hints, config.max_guess_count, tiles)
+        }
+        Some(Export::Transcript) => share::to_transcript(rounds, config.max_guess_count),
+        None => return,
+    };
+    match &config.export_path {
+        Some(path) => match std::fs::write(path, text + "\n") {
+            Ok(_) => println!("{}", Cyan.paint(format!("exported to {}", path))),
+            Err(e) => eprintln!("failed to export: {}", e),
+        },
+        None => {
+            println!();
+            println!("{}", text);
+        }
+    }
+}
+
+/// Rounds of `states` followed by `state` as the correct answer.
+fn solved_rounds(states: &InputStates, state: &InputState) -> Vec<(String, Vec<Hint>)> {
+    let mut rounds = states.rounds();
+    let word = state.word().unwrap();
+    rounds.push((word.to_string(), score(word, word)));
+    rounds
+}
+
+fn play(config: &Config, pick: Pick, assist: bool, absurdle: bool) {
+    println!("{}", Style::new().bold().paint("Welcome to WORDLE HELPER"));
+
+    let (dict_words, guess_words, weights) = extract_words(config, load_dictionary(config, true).as_ref());
+    let game = match absurdle {
+        true => Game::adversarial(&dict_words, config.max_guess_count, &guess_words),
+        false => pick_answer(&dict_words, pick).map(|answer| Game::new(answer, config.max_guess_count, &dict_words, &guess_words)),
+    };
+    let mut game = match game {
+        Some(game) => game.with_hard_mode(config.hard_mode),
+        None => {
+            eprintln!("there are no words of length {} in the dictionary", config.word_length);
+            std::process::exit(EXIT_ERROR);
+        }
+    };
     println!("{}", Cyan.paint(format!("word length: {}", config.word_length)));
     println!("{}", Cyan.paint(format!("number of answer you can guess: {}", config.max_guess_count)));
 
-    let mut helper: Box<dyn Helper> = Box::new(SimpleHelper::new(config.word_length, &dictionary.extract_words(config.word_length)));
+    let mut helper = match assist {
+        true => Some(new_helper(config, &dict_words, &guess_words, &weights)),
+        false => None,
+    };
     let mut states: InputStates = InputStates::new(config.word_length, config.max_guess_count);
 
+    while !game.is_over() {
+        println!();
+        println!("{}", Style::new().bold().paint(format!("ROUND {}/{}", states.round_count + 1, config.max_guess_count)));
+        if let Some(helper) = &helper {
+            println!("There are {} words are remained.", helper.remained_words_length().to_formatted_string(&Locale::en));
+            let suggested: Vec<String> = helper.suggest().iter()
+                .take(5)
+                .map(|word| {
+                    word.to_uppercase()
+                })
+                .collect();
+            println!("{}", Style::new().fg(RGB(128, 128, 128)).paint(format!("suggestions: {}", suggested.join(", "))));
+        }
+
+        let input: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("Guess")
+            .interact_text()
+            .unwrap();
+        let word = input.trim().to_lowercase();
+        let hints = match game.guess(&word) {
+            Ok(hints) => hints,
+            Err(e) => {
+                eprintln!("{}", e);
+                continue;
+            }
+        };
+        if let Some(helper) = &mut helper {
+            helper.add_hint(&word, &hints);
+        }
+        states.increment_round();
+        states.add(build_state(config.word_length, &word, &Hint::pattern(&hints)).unwrap());
+        println!("{}", states.preview_history().unwrap());
+    }
+
+    let status = if game.is_solved() {
+        println!("{}", Style::new().bold().paint(format!("Congratulation!! {}/{}", game.rounds().len(), config.max_guess_count)));
+        EXIT_SOLVED
+    } else {
+        println!("{}", Style::new().bold().paint(format!("X/{} GAME OVER!! The answer is {}", config.max_guess_count, game.answer().to_uppercase())));
+        EXIT_LOST
+    };
+    export_game(config, game.rounds());
+    std::process::exit(status);
+}
+
+fn load_tree(config: &Config, path: &str, dict_words: &[String]) -> Tree {
+    match Tree::load(Path::new(path)) {
+        Ok(tree) => match tree.check(config.word_length, dict_words) {
+            Ok(()) => tree,
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(EXIT_ERROR);
+            }
+        },
+        Err(e) => {
+            eprintln!("failed to load decision tree: {}", e);
+            std::process::exit(EXIT_ERROR);
+        }
+    }
+}
+
+fn run_tree(config: &Config, format: &Format, output: &Option<String>) {
+    if config.lies > 0 {
+        // Hints tolerating lies do not split answers into disjoint groups.
+        eprintln!("--lies is not supported for decision trees");
+        std::process::exit(EXIT_ERROR);
+    }
+    let (dict_words, guess_words, weights) = extract_words(config, load_dictionary(config, false).as_ref());
+    let mut helper = new_helper(config, &dict_words, &guess_words, &weights);
+    let root = match tree::build(helper.as_mut()) {
+        Some(root) => root,
+        None => {
+            eprintln!("there are no words of length {} in the dictionary", config.word_length);
+            std::process::exit(EXIT_ERROR);
+        }
+    };
+    let tree = Tree::new(config.word_length, config.strategy.to_possible_value().unwrap().get_name(), root);
+    let content = match format {
+        Format::Text => tree.text(),
+        Format::Json => serde_json::to_string_pretty(&tree).unwrap(),
+    };
+    match output {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, content + "\n") {
+                eprintln!("failed to write decision tree: {}", e);
+                std::process::exit(EXIT_ERROR);
+            }
+        }
+        None => println!("{}", content),
+    }
+}
+
+fn run_bench(config: &Config, sample: Option<usize>, seed: u64, worst: usize, format: &Format) {
+    let (dict_words, guess_words, weights) = extract_words(config, load_dictionary(config, false).as_ref());
+    let mut helper = new_helper(config, &dict_words, &guess_words, &weights);
+    let answers = match sample {
+        Some(count) => bench::sample(&dict_words, count, seed),
+        None => dict_words,
+    };
+    let report = bench::bench(helper.as_mut(), &answers, config.max_guess_count, worst);
+    match format {
+        Format::Text => println!("{}", bench_text(&report, config.max_guess_count)),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&report).unwrap()),
+    }
+}
+
+fn bench_text(report: &BenchReport, max_guess_count: usize) -> String {
+    let percent = |count: usize| {
+        if report.games == 0 { 0.0 } else { count as f64 * 100.0 / report.games as f64 }
+    };
+    let mut lines: Vec<String> = vec![
+        format!("games: {}", report.games.to_formatted_string(&Locale::en)),
+        format!("solved: {} ({:.2}%)", report.solved.to_formatted_string(&Locale::en), percent(report.solved)),
+        format!("failed: {} ({:.2}%)", report.failed.to_formatted_string(&Locale::en), percent(report.failed)),
+        format!("average guesses: {:.4}", report.average_guesses),
+        String::from("histogram:"),
+    ];
+    for guess_count in 1..=max_guess_count {
+        let count = report.histogram.get(&guess_count).cloned().unwrap_or(0);
+        lines.push(format!("  {}: {}", guess_count, count.to_formatted_string(&Locale::en)));
+    }
+    lines.push(format!("  X: {}", report.failed.to_formatted_string(&Locale::en)));
+    if !report.worst.is_empty() {
+        lines.push(String::from("worst:"));
+        for result in &report.worst {
+            let guess_count = match result.solved {
+                true => result.guesses.len().to_string(),
+                false => String::from("X"),
+            };
+            lines.push(format!("  {} {}/{} {}", result.answer, guess_count, max_guess_count, result.guesses.join(",")));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Asks a round to correct, starting at `default`, and replaces it in `states`.
+fn edit_round(config: &Config, states: &mut InputStates, default: usize) {
+    let rounds = states.rounds();
+    let items: Vec<String> = rounds.iter()
+        .enumerate()
+        .map(|(i, (word, hints))| {
+            format!("{}: {} {}", i + 1, word.to_uppercase(), Hint::pattern(hints))
+        })
+        .collect();
+    let selected = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Round to edit")
+        .default(default)
+        .items(&items)
+        .interact()
+        .unwrap();
+    let (word, hints) = &rounds[selected];
+    let word_input: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Guess")
+        .with_initial_text(word)
+        .validate_with({
+            move |input: &String| -> Result<(), &str> {
+                if input.trim().chars().count() != config.word_length {
+                    return Err("invalid length");
+                }
+                Ok(())
+            }
+        })
+        .interact_text()
+        .unwrap();
+    let hint_input: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Hint")
+        .with_initial_text(Hint::pattern(hints))
+        .validate_with({
+            let word_input = word_input.to_string();
+            move |input: &String| -> Result<(), &str> {
+                build_state(config.word_length, &word_input, input).map(|_| ())
+            }
+        })
+        .interact_text()
+        .unwrap();
+    states.replace(selected, build_state(config.word_length, &word_input, &hint_input).unwrap());
+}
+
+fn interactive(config: &Config) {
+    println!("{}", Style::new().bold().paint("Welcome to WORDLE HELPER"));
+
+    let mut config = config.clone();
+    let session_path = Session::default_path();
+    let mut session = load_session(&mut config, &session_path);
+
+    let dictionary = load_dictionary(&config, true);
+    println!("{}", Cyan.paint(format!("word length: {}", config.word_length)));
+    println!("{}", Cyan.paint(format!("number of answer you can guess: {}", config.max_guess_count)));
+
+    let (dict_words, guess_words, weights) = extract_words(&config, dictionary.as_ref());
+    let mut helper = new_helper(&config, &dict_words, &guess_words, &weights);
+    let mut states: InputStates = InputStates::new(config.word_length, config.max_guess_count);
+
+    if let Some(path) = &config.from_share {
+        for (word, hints) in load_share_rounds(&config, path, true) {
+            session.add_round(&word, &Hint::pattern(&hints));
+        }
+    }
+    for round in &session.rounds {
+        let mut state = match build_state(config.word_length, &round.word, &round.pattern) {
+            Ok(state) => state,
+            Err(e) => {
+                eprintln!("failed to replay \"{}:{}\": {}", round.word, round.pattern, e);
+                std::process::exit(EXIT_ERROR);
+            }
+        };
+        let (word, hints) = state.get().unwrap();
+        if Hint::all_at(hints) {
+            state.correct();
+            finish_session(&session_path);
+            println!("{}", Style::new().bold().paint("This game is already solved."));
+            println!("{}", states.preview(&state).unwrap());
+            export_game(&config, &solved_rounds(&states, &state));
+            std::process::exit(0);
+        }
+        helper.add_hint(word, hints);
+        states.increment_round();
+        states.add(state);
+    }
+    if !session.rounds.is_empty() {
+        println!("{}", Cyan.paint(format!("replayed {} rounds", session.rounds.len())));
+        println!("{}", states.preview_history().unwrap());
+        if states.round_count as usize >= config.max_guess_count {
+            finish_session(&session_path);
+            println!("{}", Style::new().bold().paint(format!("X/{} GAME OVER!!", config.max_guess_count)));
+            export_game(&config, &states.rounds());
+            std::process::exit(1);
+        }
+        save_session(&session, &session_path);
+    }
+
     loop {
         let remained_words_length = helper.remained_words_length();
         if remained_words_length == 0 {
             println!();
-            eprintln!("Sorry, there are no matched words. quit.");
-            std::process::exit(1);
+            if states.is_empty() {
+                eprintln!("Sorry, there are no matched words. quit.");
+                std::process::exit(EXIT_NO_MATCH);
+            }
+            eprintln!("Sorry, there are no matched words.");
+            let diagnosis = Diagnosis::new(&dict_words, &states.rounds());
+            println!("{}", Style::new().fg(RGB(128, 128, 128)).paint(diagnosis.text(DIAGNOSIS_NEAR_MISSES)));
+            if !Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt("Do you want to fix a round?")
+                .default(true)
+                .interact()
+                .unwrap()
+            {
+                // The session is kept, so the game can be fixed later with --resume.
+                std::process::exit(EXIT_NO_MATCH);
+            }
+            let default = diagnosis.suspects().first().copied().unwrap_or(states.rounds().len() - 1);
+            edit_round(&config, &mut states, default);
+            replay(helper.as_mut(), &mut session, &states);
+            save_session(&session, &session_path);
+            println!("{}", states.preview_history().unwrap());
+            continue;
         }
 
         println!();
@@ -58,34 +937,56 @@ fn main() {
 
         let mut state = InputState::new(config.word_length);
 
-        let guess_types = &[
-            "Use suggestions",
-            "Input manually",
+        let mut guess_types = vec![
+            GUESS_SUGGESTIONS,
+            GUESS_MANUALLY,
         ];
+        if !states.is_empty() {
+            guess_types.push(UNDO_ROUND);
+            guess_types.push(EDIT_ROUND);
+        }
         let selected_type_idx = Select::with_theme(&ColorfulTheme::default())
             .with_prompt("Select guessing type")
             .default(0)
             .items(&guess_types[..])
             .interact()
             .unwrap();
-        match selected_type_idx {
-            0 => {
+        match guess_types[selected_type_idx] {
+            GUESS_SUGGESTIONS => {
                 let suggested = helper.suggest();
+                let items: Vec<String> = match helper.scores() {
+                    Some(scores) => zip(suggested, scores)
+                        .map(|(word, score)| {
+                            match helper.probability(word) {
+                                Some(probability) => format!("{} ({:.2} {}, {:.1}% to be the answer)",
+                                                             word, score, config.strategy.score_unit(), probability * 100.0),
+                                None => format!("{} ({:.2} {})", word, score, config.strategy.score_unit()),
+                            }
+                        })
+                        .collect(),
+                    None => suggested.clone(),
+                };
                 let selected = FuzzySelect::with_theme(&ColorfulTheme::default())
                     .with_prompt("Guess")
                     .default(0)
-                    .items(suggested)
+                    .items(&items)
                     .interact()
                     .unwrap();
                 state.add_word(&suggested[selected]).unwrap();
             }
-            1 => {
+            GUESS_MANUALLY => {
+                let history = states.hints();
                 let input: String = Input::with_theme(&ColorfulTheme::default())
                     .with_prompt("Guess")
                     .validate_with({
-                        move |input: &String| -> Result<(), &str> {
+                        move |input: &String| -> Result<(), String> {
                             if input.len() != config.word_length {
-                                return Err("invalid length");
+                                return Err(String::from("invalid length"));
+                            }
+                            if config.hard_mode {
+                                for hints in &history {
+                                    check_hard_mode(input, hints)?;
+                                }
                             }
                             Ok(())
                         }
@@ -94,6 +995,21 @@ fn main() {
                     .unwrap();
                 state.add_word(&input).unwrap();
             }
+            UNDO_ROUND => {
+                states.pop();
+                replay(helper.as_mut(), &mut session, &states);
+                save_session(&session, &session_path);
+                println!("{}", states.preview_history().unwrap());
+                continue;
+            }
+            EDIT_ROUND => {
+                let last = states.rounds().len() - 1;
+                edit_round(&config, &mut states, last);
+                replay(helper.as_mut(), &mut session, &states);
+                save_session(&session, &session_path);
+                println!("{}", states.preview_history().unwrap());
+                continue;
+            }
             _ => {
                 eprintln!("failed to recognize selection");
                 std::process::exit(1);
@@ -109,16 +1025,13 @@ fn main() {
             .unwrap()
         {
             state.correct();
+            finish_session(&session_path);
             println!("{}", Style::new().bold().paint("Congratulation!!"));
             println!("{}", states.preview(&state).unwrap());
+            export_game(&config, &solved_rounds(&states, &state));
             std::process::exit(0);
         }
 
-        if states.is_final_round() {
-            println!("{}", Style::new().bold().paint(format!("X/{} GAME OVER!!", config.max_guess_count)));
-            println!("{}", states.preview(&state).unwrap());
-            std::process::exit(1);
-        }
         states.increment_round();
 
         loop {
@@ -175,22 +1088,191 @@ fn main() {
                 let (word, hints) = state.get().unwrap();
                 if Hint::all_at(hints) {
                     state.correct();
+                    finish_session(&session_path);
                     println!("{}", Style::new().bold().paint("Wow, It's correct! Congrats!"));
                     println!("{}", states.preview(&state).unwrap());
+                    export_game(&config, &solved_rounds(&states, &state));
                     std::process::exit(0);
                 }
                 helper.add_hint(word, hints);
+                session.add_round(word, &Hint::pattern(hints));
+                save_session(&session, &session_path);
                 states.add(state);
+                if states.round_count as usize >= config.max_guess_count {
+                    finish_session(&session_path);
+                    println!("{}", Style::new().bold().paint(format!("X/{} GAME OVER!!", config.max_guess_count)));
+                    println!("{}", states.preview_history().unwrap());
+                    export_game(&config, &states.rounds());
+                    std::process::exit(1);
+                }
                 break;
             }
         }
     }
 }
 
+fn interactive_boards(config: &Config) {
+    println!("{}", Style::new().bold().paint("Welcome to WORDLE HELPER"));
+
+    let dictionary = load_dictionary(config, true);
+    println!("{}", Cyan.paint(format!("word length: {}", config.word_length)));
+    println!("{}", Cyan.paint(format!("number of answer you can guess: {}", config.max_guess_count)));
+    println!("{}", Cyan.paint(format!("number of boards: {}", config.boards)));
+
+    let (dict_words, guess_words, weights) = extract_words(config, dictionary.as_ref());
+    if !weights.is_empty() {
+        eprintln!("--boards does not support dictionaries of weights");
+        std::process::exit(EXIT_ERROR);
+    }
+    let mut helper = MultiHelper::with_guesses(config.word_length, &dict_words, &guess_words, config.boards);
+    let mut boards: Vec<InputStates> = (0..config.boards)
+        .map(|_| {
+            InputStates::new(config.word_length, config.max_guess_count)
+        })
+        .collect();
+
+    for round in 0..config.max_guess_count {
+        let remained: Vec<String> = (0..helper.board_count())
+            .map(|i| {
+                match (helper.is_solved(i), helper.remained_words_length(i)) {
+                    (true, _) => String::from("solved"),
+                    (false, 0) => {
+                        println!();
+                        eprintln!("Sorry, there are no matched words on board {}. quit.", i + 1);
+                        std::process::exit(EXIT_NO_MATCH);
+                    }
+                    (false, length) => length.to_formatted_string(&Locale::en),
+                }
+            })
+            .collect();
+
+        println!();
+        println!("{}", Style::new().bold().paint(format!("ROUND {}/{}", round + 1, config.max_guess_count)));
+        println!("Remained words by board: {}", remained.join(", "));
+
+        let guess_types = [
+            GUESS_SUGGESTIONS,
+            GUESS_MANUALLY,
+        ];
+        let selected_type_idx = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Select guessing type")
+            .default(0)
+            .items(&guess_types[..])
+            .interact()
+            .unwrap();
+        let word = match guess_types[selected_type_idx] {
+            GUESS_SUGGESTIONS => {
+                let suggested = helper.suggest();
+                let items: Vec<String> = zip(suggested, helper.scores())
+                    .map(|(word, score)| {
+                        format!("{} ({:.2} bits)", word, score)
+                    })
+                    .collect();
+                let selected = FuzzySelect::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Guess")
+                    .default(0)
+                    .items(&items)
+                    .interact()
+                    .unwrap();
+                suggested[selected].to_string()
+            }
+            _ => Input::with_theme(&ColorfulTheme::default())
+                .with_prompt("Guess")
+                .validate_with({
+                    move |input: &String| -> Result<(), &str> {
+                        if input.trim().chars().count() != config.word_length {
+                            return Err("invalid length");
+                        }
+                        Ok(())
+                    }
+                })
+                .interact_text()
+                .unwrap()
+                .trim()
+                .to_string(),
+        };
+
+        let mut hints: Vec<Vec<Hint>> = Vec::new();
+        for (i, states) in boards.iter_mut().enumerate() {
+            if helper.is_solved(i) {
+                hints.push(Vec::new());
+                continue;
+            }
+            let hint_input: String = Input::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("Hint of board {}", i + 1))
+                .validate_with({
+                    let word = word.to_string();
+                    move |input: &String| -> Result<(), &str> {
+                        build_state(config.word_length, &word, input).map(|_| ())
+                    }
+                })
+                .interact_text()
+                .unwrap();
+            let mut state = build_state(config.word_length, &word, &hint_input).unwrap();
+            let (_, board_hints) = state.get().unwrap();
+            hints.push(board_hints.clone());
+            if Hint::all_at(board_hints) {
+                state.correct();
+            }
+            states.increment_round();
+            states.add(state);
+        }
+        helper.add_hints(&word, &hints);
+
+        let grids: Vec<String> = boards.iter()
+            .map(|states| {
+                states.preview_history().unwrap()
+            })
+            .collect();
+        println!("{}", side_by_side(&grids));
+
+        if helper.is_all_solved() {
+            println!("{}", Style::new().bold().paint(format!("Congratulation!! {}/{}", round + 1, config.max_guess_count)));
+            std::process::exit(0);
+        }
+    }
+    println!("{}", Style::new().bold().paint(format!("X/{} GAME OVER!!", config.max_guess_count)));
+    std::process::exit(1);
+}
+
+/// Joins lines of multi-line `blocks` so that they are rendered side by side.
+fn side_by_side(blocks: &[String]) -> String {
+    let lines: Vec<Vec<&str>> = blocks.iter()
+        .map(|block| {
+            block.lines().collect()
+        })
+        .collect();
+    let height = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+    (0..height)
+        .map(|i| {
+            lines.iter()
+                .map(|l| {
+                    l.get(i).copied().unwrap_or("")
+                })
+                .collect::<Vec<&str>>()
+                .join(" ")
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
 const BACK_GREEN: Colour = RGB(83, 141, 78);
 const BACK_YELLOW: Colour = RGB(180, 159, 58);
 const BACK_GRAY: Colour = RGB(58, 58, 60);
 
+fn colorize_pattern(pattern: &str) -> String {
+    pattern.chars()
+        .map(|c| {
+            let hint_type = match c {
+                '2' => HintInputType::Just,
+                '1' => HintInputType::Somewhere,
+                _ => HintInputType::Nowhere,
+            };
+            format!("{}", colorize(&hint_type, " "))
+        })
+        .collect()
+}
+
 enum HintInputType {
     Nowhere,
     Somewhere,
@@ -198,11 +1280,11 @@ enum HintInputType {
 }
 
 fn colorize<'a>(hint_type: &'a HintInputType, text: &'a str) -> ANSIGenericString<'a, str> {
-    return match hint_type {
+    match hint_type {
         HintInputType::Nowhere => Style::new().on(BACK_GRAY).fg(White).bold().paint(text.to_string()),
         HintInputType::Somewhere => Style::new().on(BACK_YELLOW).fg(White).bold().paint(text.to_string()),
         HintInputType::Just => Style::new().on(BACK_GREEN).fg(White).bold().paint(text.to_string()),
-    };
+    }
 }
 
 struct InputState {
@@ -238,23 +1320,7 @@ impl InputState {
         if trimmed.len() != self.word_length {
             return Result::Err("invalid length");
         }
-        for (i, hint_c) in trimmed.chars().enumerate() {
-            let word_c = self.word.as_ref().unwrap().chars().nth(i).unwrap();
-            match hint_c {
-                '0' => {
-                    self.hint.push(Hint::new(word_c, Spot::None()));
-                }
-                '1' => {
-                    self.hint.push(Hint::new(word_c, Spot::InWithout(i)));
-                }
-                '2' => {
-                    self.hint.push(Hint::new(word_c, Spot::At(i)));
-                }
-                _ => {
-                    return Result::Err("input must be 0,1,2");
-                }
-            }
-        }
+        self.hint = Hint::from_pattern(self.word.as_ref().unwrap(), trimmed)?;
         Result::Ok(())
     }
 
@@ -312,10 +1378,10 @@ impl InputState {
         if self.hint.is_empty() {
             return Result::Err("hints are empty");
         }
-        return Result::Ok((
+        Result::Ok((
             self.word.as_ref().unwrap(),
             &self.hint,
-        ));
+        ))
     }
 }
 
@@ -335,16 +1401,40 @@ impl InputStates {
         self.states.push(state)
     }
 
-    pub fn preview(&self, staged_state: &InputState) -> Result<String, &'static str> {
-        let mut results: Vec<String> = Vec::new();
-        for state in &self.states {
-            match state.colorized() {
-                Ok(s) => results.push(s),
-                Err(e) => {
-                    return Err(e);
-                }
-            }
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    pub fn pop(&mut self) -> Option<InputState> {
+        let state = self.states.pop();
+        if state.is_some() {
+            self.round_count -= 1;
         }
+        state
+    }
+
+    pub fn replace(&mut self, index: usize, state: InputState) {
+        self.states[index] = state;
+    }
+
+    pub fn rounds(&self) -> Vec<(String, Vec<Hint>)> {
+        self.states.iter()
+            .map(|state| {
+                (state.word().unwrap().to_string(), state.hint.clone())
+            })
+            .collect()
+    }
+
+    pub fn hints(&self) -> Vec<Vec<Hint>> {
+        self.states.iter()
+            .map(|state| {
+                state.hint.clone()
+            })
+            .collect()
+    }
+
+    pub fn preview(&self, staged_state: &InputState) -> Result<String, &'static str> {
+        let mut results = self.colorized_history()?;
 
         let staged_text = match staged_state.is_draft() {
             true => staged_state.plain(),
@@ -359,6 +1449,24 @@ impl InputStates {
         Ok(InputStates::pretty_preview(self, &results))
     }
 
+    pub fn preview_history(&self) -> Result<String, &'static str> {
+        let results = self.colorized_history()?;
+        Ok(InputStates::pretty_preview(self, &results))
+    }
+
+    fn colorized_history(&self) -> Result<Vec<String>, &'static str> {
+        let mut results: Vec<String> = Vec::new();
+        for state in &self.states {
+            match state.colorized() {
+                Ok(s) => results.push(s),
+                Err(e) => {
+                    return Err(e);
+                }
+            }
+        }
+        Ok(results)
+    }
+
     fn pretty_preview(&self, word_strs: &[String]) -> String {
         let header_footer: String = format!("+{}+", "-".repeat(self.word_length));
         let mut results: Vec<String> = vec![header_footer.clone()];
@@ -376,16 +1484,40 @@ impl InputStates {
     fn increment_round(&mut self) {
         self.round_count += 1;
     }
-
-    fn is_final_round(&self) -> bool {
-        (self.round_count + 1) == self.max_guess_count as i32
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn config() {
+        use clap::CommandFactory;
+        Config::command().debug_assert();
+    }
+
+    #[test]
+    fn tree_conflicts() {
+        assert!(Config::try_parse_from(["wordle-helper", "--tree", "tree.json"]).is_ok());
+        assert!(Config::try_parse_from(["wordle-helper", "--tree", "tree.json", "-s", "entropy"]).is_err());
+        assert!(Config::try_parse_from(["wordle-helper", "--tree", "tree.json", "--hard-mode"]).is_err());
+        assert!(Config::try_parse_from(["wordle-helper", "solve", "--tree", "tree.json", "--lies", "1"]).is_err());
+    }
+
+    #[test]
+    fn lies_conflicts() {
+        assert!(Config::try_parse_from(["wordle-helper", "--lies", "1", "-s", "entropy"]).is_ok());
+        assert!(Config::try_parse_from(["wordle-helper", "--lies", "1", "--hard-mode"]).is_err());
+        assert!(Config::try_parse_from(["wordle-helper", "solve", "--hard-mode", "--lies", "1"]).is_err());
+    }
+
+    #[test]
+    fn boards_conflicts() {
+        assert!(Config::try_parse_from(["wordle-helper", "--boards", "2"]).is_ok());
+        assert!(Config::try_parse_from(["wordle-helper", "--boards", "2", "-s", "minimax"]).is_err());
+        assert!(Config::try_parse_from(["wordle-helper", "--boards", "2", "--hard-mode"]).is_err());
+    }
+
     #[cfg(test)]
     mod input_state {
         use super::*;
@@ -445,6 +1577,16 @@ mod tests {
                 }
             }
 
+            #[test]
+            fn replace_previous() {
+                let mut state = InputState::new(5);
+                state.add_word("bound").unwrap();
+                state.add_hint("00120").unwrap();
+                state.add_hint("00000").unwrap();
+                assert_eq!(state.hint.len(), 5);
+                assert_eq!(Hint::pattern(&state.hint), "00000");
+            }
+
             #[test]
             fn invalid_no_word() {
                 let mut state = InputState::new(5);
@@ -454,10 +1596,187 @@ mod tests {
         }
     }
 
+    #[cfg(test)]
+    mod parse_round {
+        use super::*;
+
+        #[test]
+        fn valid() {
+            let state = parse_round(5, "bound:00120").unwrap();
+            assert_eq!(state.word(), Some(&String::from("bound")));
+            assert_eq!(state.hint, vec![
+                Hint::new('b', Spot::None()),
+                Hint::new('o', Spot::None()),
+                Hint::new('u', Spot::InWithout(2)),
+                Hint::new('n', Spot::At(3)),
+                Hint::new('d', Spot::None()),
+            ]);
+        }
+
+        #[test]
+        fn invalid() {
+            let inputs = vec!["bound", "bound:0012", "bounds:00120", "bound:0012a"];
+            for input in inputs {
+                assert!(parse_round(5, input).is_err());
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod solve_report {
+        use super::*;
+
+        fn preset_words() -> Vec<String> {
+            vec!["early".to_string(), "apple".to_string(), "asset".to_string(), "bound".to_string()]
+        }
+
+        #[test]
+        fn json_remained() {
+            let states = vec![parse_round(5, "bound:00000").unwrap()];
+            let mut helper = SimpleHelper::new(5, &preset_words());
+            let (word, hints) = states[0].get().unwrap();
+            helper.add_hint(word, hints);
+            let report = SolveReport::new(5, &states, &helper, 2, None);
+            assert_eq!(serde_json::to_string_pretty(&report).unwrap(), r#"{
+  "version": 1,
+  "word_length": 5,
+  "rounds": [
+    {
+      "guess": "bound",
+      "pattern": "00000"
+    }
+  ],
+  "solved": false,
+  "remained_count": 3,
+  "remained_words": [
+    "early",
+    "apple",
+    "asset"
+  ],
+  "suggestions": [
+    {
+      "word": "early",
+      "score": null
+    },
+    {
+      "word": "apple",
+      "score": null
+    }
+  ]
+}"#)
+        }
+
+        #[test]
+        fn json_scores() {
+            let helper = InformationHelper::new(5, &["early".to_string(), "asset".to_string()]);
+            let report = SolveReport::new(5, &[], &helper, 1, None);
+            assert_eq!(serde_json::to_string(&report).unwrap(),
+                       r#"{"version":1,"word_length":5,"rounds":[],"solved":false,"remained_count":2,"remained_words":["early","asset"],"suggestions":[{"word":"early","score":1.0}]}"#)
+        }
+
+        #[test]
+        fn json_probabilities() {
+            let weights: Weights = [("early".to_string(), 3.0)].into_iter().collect();
+            let helper = InformationHelper::with_weights(5, &["early".to_string(), "asset".to_string()], &[], weights);
+            let report = SolveReport::new(5, &[], &helper, 1, None);
+            assert_eq!(serde_json::to_string(&report).unwrap(),
+                       r#"{"version":1,"word_length":5,"rounds":[],"solved":false,"remained_count":2,"remained_words":["early","asset"],"suggestions":[{"word":"early","score":0.8112781244591328,"probability":0.75}]}"#);
+            assert_eq!(report.text(), "early\nasset\n\nearly 0.8113 0.7500");
+        }
+
+        #[test]
+        fn json_solved() {
+            let states = vec![parse_round(5, "early:22222").unwrap()];
+            let helper = SimpleHelper::new(5, &preset_words());
+            let report = SolveReport::new(5, &states, &helper, 3, Some("early"));
+            assert_eq!(serde_json::to_string(&report).unwrap(),
+                       r#"{"version":1,"word_length":5,"rounds":[{"guess":"early","pattern":"22222"}],"solved":true,"remained_count":1,"remained_words":["early"],"suggestions":[]}"#)
+        }
+
+        #[test]
+        fn text() {
+            let helper = InformationHelper::new(5, &["early".to_string(), "asset".to_string()]);
+            let report = SolveReport::new(5, &[], &helper, 1, None);
+            assert_eq!(report.text(), "early\nasset\n\nearly 1.0000");
+        }
+    }
+
+    #[test]
+    fn side_by_side_blocks() {
+        let blocks = vec![String::from("+--+\n|ab|\n+--+"), String::from("+--+\n|cd|\n+--+")];
+        assert_eq!(side_by_side(&blocks), "+--+ +--+\n|ab| |cd|\n+--+ +--+");
+    }
+
+    #[test]
+    fn bench_report_text() {
+        let mut helper = SimpleHelper::new(5, &["hello".to_string(), "early".to_string(), "asset".to_string()]);
+        let answers = vec!["hello".to_string(), "asset".to_string(), "zzzzz".to_string()];
+        let report = bench::bench(&mut helper, &answers, 3, 2);
+        assert_eq!(bench_text(&report, 3), r#"games: 3
+solved: 2 (66.67%)
+failed: 1 (33.33%)
+average guesses: 1.5000
+histogram:
+  1: 1
+  2: 1
+  3: 0
+  X: 1
+worst:
+  zzzzz X/3 hello
+  asset 2/3 hello,asset"#)
+    }
+
     #[cfg(test)]
     mod input_states {
         use super::*;
 
+        fn preset_states() -> InputStates {
+            let mut target = InputStates::new(5, 6);
+            for round in ["bound:00000", "spice:10001"] {
+                target.increment_round();
+                target.add(parse_round(5, round).unwrap());
+            }
+            target
+        }
+
+        #[test]
+        fn pop() {
+            let mut target = preset_states();
+            assert_eq!(target.pop().unwrap().word(), Some(&String::from("spice")));
+            assert_eq!(target.round_count, 1);
+            assert_eq!(target.rounds().len(), 1);
+            target.pop();
+            assert!(target.is_empty());
+            assert!(target.pop().is_none());
+            assert_eq!(target.round_count, 0);
+        }
+
+        #[test]
+        fn replace() {
+            let mut target = preset_states();
+            target.replace(0, parse_round(5, "bound:01000").unwrap());
+            let patterns: Vec<String> = target.rounds().iter()
+                .map(|(_, hints)| {
+                    Hint::pattern(hints)
+                })
+                .collect();
+            assert_eq!(patterns, vec!["01000", "10001"]);
+            assert_eq!(target.round_count, 2);
+        }
+
+        #[test]
+        fn rounds_with_answer() {
+            let target = preset_states();
+            let mut state = InputState::new(5);
+            state.add_word("asset").unwrap();
+            let patterns: Vec<String> = solved_rounds(&target, &state).iter()
+                .map(|(_, hints)| {
+                    Hint::pattern(hints)
+                })
+                .collect();
+            assert_eq!(patterns, vec!["00000", "10001", "22222"]);
+        }
+
         #[test]
         fn pretty_preview() {
             let target = InputStates::new(5, 6);
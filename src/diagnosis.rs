@@ -0,0 +1,274 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{Hint, ordinal, score, Spot};
+use crate::simple::SimpleHelper;
+
+/// Two rounds whose hints cannot be true at once.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Contradiction {
+    /// Indexes of the earlier and the later round.
+    pub rounds: (usize, usize),
+    pub letter: char,
+    pub reason: String,
+}
+
+/// A word which would remain if not for hints of one round.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct NearMiss {
+    pub word: String,
+    pub round: usize,
+    /// Positions whose hints would have to differ for the word to remain.
+    pub tiles: Vec<usize>,
+}
+
+/// Explains why no words are remained for rounds of guesses and hints.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Diagnosis {
+    pub contradictions: Vec<Contradiction>,
+    /// Sorted from the fewest tiles to change.
+    pub near_misses: Vec<NearMiss>,
+}
+
+impl Diagnosis {
+    pub fn new(dict_words: &[String], rounds: &[(String, Vec<Hint>)]) -> Self {
+        Diagnosis {
+            contradictions: contradictions(rounds),
+            near_misses: near_misses(dict_words, rounds),
+        }
+    }
+
+    /// Indexes of rounds which are likely mistyped, from the most suspicious.
+    pub fn suspects(&self) -> Vec<usize> {
+        let mut counts: HashMap<usize, usize> = HashMap::new();
+        for contradiction in &self.contradictions {
+            *counts.entry(contradiction.rounds.0).or_insert(0) += 1;
+            *counts.entry(contradiction.rounds.1).or_insert(0) += 1;
+        }
+        for near_miss in self.near_misses.iter().filter(|n| n.tiles.len() == 1) {
+            *counts.entry(near_miss.round).or_insert(0) += 1;
+        }
+        let mut suspects: Vec<(usize, usize)> = counts.into_iter().collect();
+        suspects.sort_by(|a, b| {
+            b.1.cmp(&a.1).then(a.0.cmp(&b.0))
+        });
+        suspects.into_iter()
+            .map(|(round, _)| {
+                round
+            })
+            .collect()
+    }
+
+    /// Formats contradictions and up to `near_miss_count` near misses with rounds counted from 1.
+    pub fn text(&self, near_miss_count: usize) -> String {
+        let mut lines: Vec<String> = Vec::new();
+        if !self.contradictions.is_empty() {
+            lines.push(String::from("contradictions:"));
+            for contradiction in &self.contradictions {
+                lines.push(format!("  {}", contradiction.reason));
+            }
+        }
+        if !self.near_misses.is_empty() {
+            lines.push(String::from("words eliminated by only one round:"));
+            for near_miss in self.near_misses.iter().take(near_miss_count) {
+                let tiles: Vec<String> = near_miss.tiles.iter()
+                    .map(|tile| {
+                        ordinal(tile + 1)
+                    })
+                    .collect();
+                lines.push(format!("  {} by round {} ({} letter)", near_miss.word, near_miss.round + 1, tiles.join(", ")));
+            }
+        }
+        if lines.is_empty() {
+            lines.push(String::from("no contradictions are found"));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Finds pairs of rounds whose hints contradict each other.
+pub fn contradictions(rounds: &[(String, Vec<Hint>)]) -> Vec<Contradiction> {
+    let mut results: Vec<Contradiction> = Vec::new();
+    for (a, (_, hints_a)) in rounds.iter().enumerate() {
+        for (b, (_, hints_b)) in rounds.iter().enumerate().skip(a + 1) {
+            results.extend(contradict_spots(a, hints_a, b, hints_b, true));
+            results.extend(contradict_spots(b, hints_b, a, hints_a, false));
+            results.extend(contradict_counts(a, hints_a, b, hints_b));
+        }
+    }
+    results
+}
+
+/// Checks letters at the spot in round `x` against the same positions in round `y`.
+///
+/// Different letters at the spot in both rounds are only checked with `at_both`,
+/// so that they are not reported twice.
+fn contradict_spots(x: usize, hints_x: &[Hint], y: usize, hints_y: &[Hint], at_both: bool) -> Vec<Contradiction> {
+    let mut results: Vec<Contradiction> = Vec::new();
+    for hint_x in hints_x {
+        let at = match hint_x.spot {
+            Spot::At(at) => at,
+            _ => continue,
+        };
+        let hint_y = match hints_y.get(at) {
+            Some(hint) => hint,
+            None => continue,
+        };
+        let reason = match (&hint_y.spot, hint_y.letter == hint_x.letter) {
+            (Spot::At(_), false) if at_both => format!("round {} has {} as the {} letter, but round {} has {}",
+                                                       x + 1, hint_x.letter.to_uppercase(), ordinal(at + 1), y + 1, hint_y.letter.to_uppercase()),
+            (Spot::InWithout(_), true) | (Spot::None(), true) => format!("round {} has {} as the {} letter, but round {} marks it not there",
+                                                                         x + 1, hint_x.letter.to_uppercase(), ordinal(at + 1), y + 1),
+            _ => continue,
+        };
+        results.push(Contradiction { rounds: (x.min(y), x.max(y)), letter: hint_x.letter, reason });
+    }
+    results
+}
+
+/// Checks how many times each letter appears between rounds `a` and `b`.
+fn contradict_counts(a: usize, hints_a: &[Hint], b: usize, hints_b: &[Hint]) -> Vec<Contradiction> {
+    let counts_a = SimpleHelper::count_letters(hints_a);
+    let counts_b = SimpleHelper::count_letters(hints_b);
+    let mut letters: Vec<&char> = counts_a.keys().filter(|letter| counts_b.contains_key(letter)).collect();
+    letters.sort();
+    let mut results: Vec<Contradiction> = Vec::new();
+    for letter in letters {
+        for ((x, count_x), (y, count_y)) in [((a, &counts_a[letter]), (b, &counts_b[letter])), ((b, &counts_b[letter]), (a, &counts_a[letter]))] {
+            if let Some(max) = count_y.max {
+                if count_x.min > max {
+                    results.push(Contradiction {
+                        rounds: (a, b),
+                        letter: *letter,
+                        reason: format!("round {} needs at least {} {}, but round {} allows at most {}",
+                                        x + 1, count_x.min, letter.to_uppercase(), y + 1, max),
+                    });
+                }
+            }
+        }
+    }
+    results
+}
+
+/// Finds `dict_words` which match hints of every round but one, except the guessed words.
+pub fn near_misses(dict_words: &[String], rounds: &[(String, Vec<Hint>)]) -> Vec<NearMiss> {
+    let width = match rounds.first() {
+        Some((word, _)) => word.chars().count(),
+        None => return Vec::new(),
+    };
+    let mut results: Vec<NearMiss> = Vec::new();
+    let guessed: HashSet<&str> = rounds.iter()
+        .map(|(guess, _)| {
+            guess.as_str()
+        })
+        .collect();
+    for word in dict_words.iter().filter(|w| w.chars().count() == width && !guessed.contains(w.as_str())) {
+        let failed: Vec<usize> = rounds.iter()
+            .enumerate()
+            .filter(|(_, (_, hints))| {
+                !SimpleHelper::matches(word, hints)
+            })
+            .map(|(i, _)| {
+                i
+            })
+            .take(2)
+            .collect();
+        if failed.len() != 1 {
+            continue;
+        }
+        let (guess, hints) = &rounds[failed[0]];
+        let tiles: Vec<usize> = Hint::pattern(&score(guess, word)).chars()
+            .zip(Hint::pattern(hints).chars())
+            .enumerate()
+            .filter(|(_, (actual, input))| {
+                actual != input
+            })
+            .map(|(i, _)| {
+                i
+            })
+            .collect();
+        results.push(NearMiss { word: word.to_string(), round: failed[0], tiles });
+    }
+    results.sort_by_key(|near_miss| near_miss.tiles.len());
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round(word: &str, pattern: &str) -> (String, Vec<Hint>) {
+        (word.to_string(), Hint::from_pattern(word, pattern).unwrap())
+    }
+
+    #[cfg(test)]
+    mod contradictions {
+        use super::*;
+
+        #[test]
+        fn different_letters_at_same_spot() {
+            let actual = contradictions(&[round("bound", "00200"), round("early", "00200")]);
+            assert_eq!(actual.len(), 1);
+            assert_eq!(actual[0].rounds, (0, 1));
+            assert_eq!(actual[0].reason, "round 1 has U as the 3rd letter, but round 2 has R");
+        }
+
+        #[test]
+        fn somewhere_and_gray() {
+            let actual = contradictions(&[round("bound", "00000"), round("hello", "00001")]);
+            assert_eq!(actual.len(), 1);
+            assert_eq!(actual[0].letter, 'o');
+            assert_eq!(actual[0].reason, "round 2 needs at least 1 O, but round 1 allows at most 0");
+        }
+
+        #[test]
+        fn at_and_gray() {
+            let actual = contradictions(&[round("early", "20000"), round("eerie", "00000")]);
+            assert_eq!(actual[0].rounds, (0, 1));
+            assert_eq!(actual[0].letter, 'e');
+            assert_eq!(actual[0].reason, "round 1 has E as the 1st letter, but round 2 marks it not there");
+        }
+
+        #[test]
+        fn at_and_somewhere() {
+            let actual = contradictions(&[round("early", "20000"), round("eerie", "10000")]);
+            assert_eq!(actual[0].rounds, (0, 1));
+            assert_eq!(actual[0].reason, "round 1 has E as the 1st letter, but round 2 marks it not there");
+        }
+
+        #[test]
+        fn consistent() {
+            assert!(contradictions(&[round("hello", "01020"), round("asset", "10010")]).is_empty());
+        }
+    }
+
+    #[test]
+    fn near_misses_by_one_tile() {
+        let words = vec!["early".to_string(), "bound".to_string()];
+        let actual = near_misses(&words, &[round("hello", "01020"), round("sweat", "00100")]);
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0], NearMiss { word: String::from("early"), round: 1, tiles: vec![3] });
+    }
+
+    #[test]
+    fn near_misses_without_guesses() {
+        let words = vec!["early".to_string(), "hello".to_string()];
+        let actual = near_misses(&words, &[round("hello", "01020"), round("sweat", "00100")]);
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0].word, "early");
+    }
+
+    #[test]
+    fn suspects() {
+        let words = vec!["early".to_string()];
+        let target = Diagnosis::new(&words, &[round("hello", "01020"), round("sweat", "00100")]);
+        assert!(target.contradictions.is_empty());
+        assert_eq!(target.suspects(), vec![1]);
+        assert_eq!(target.text(10), "words eliminated by only one round:\n  early by round 2 (4th letter)");
+    }
+
+    #[test]
+    fn text_without_problems() {
+        let target = Diagnosis::new(&[], &[]);
+        assert_eq!(target.text(10), "no contradictions are found");
+    }
+}
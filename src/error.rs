@@ -0,0 +1,50 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+/// Errors of loading dictionaries.
+#[derive(Debug)]
+pub enum Error {
+    /// No file is found at the path.
+    NotFound(PathBuf),
+    /// Downloading the default dictionary failed, with the reason.
+    Download(String),
+    /// A line of the file is not valid UTF-8, with the line number counted from 1.
+    InvalidLine { path: PathBuf, line: usize },
+    /// The weight of a line is not a non-negative number, with the line number counted from 1.
+    InvalidWeight { path: PathBuf, line: usize },
+    /// The dictionary has no words of the length.
+    Empty { word_length: usize },
+    /// The default dictionary is missing and downloading it is declined.
+    DownloadDeclined,
+    Io(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotFound(path) => write!(f, "dictionary is not found at {}", path.display()),
+            Error::Download(reason) => write!(f, "failed to download dictionary: {}", reason),
+            Error::InvalidLine { path, line } => write!(f, "line {} of {} is not valid UTF-8", line, path.display()),
+            Error::InvalidWeight { path, line } => write!(f, "weight at line {} of {} is not a non-negative number", line, path.display()),
+            Error::Empty { word_length } => write!(f, "there are no words of length {} in the dictionary", word_length),
+            Error::DownloadDeclined => write!(f, "downloading the default dictionary is declined"),
+            Error::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
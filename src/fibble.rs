@@ -0,0 +1,289 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{Hint, Helper, marks, Spot};
+use crate::ranked::{RankedHelper, Ranking};
+
+/// A helper for variants like Fibble, where up to `max_lies` tiles of every
+/// row may show a wrong color.
+///
+/// A word remains while its true hints differ from the shown hints in at most
+/// `max_lies` tiles, so it is never eliminated by a lie the strict filter of
+/// `SimpleHelper` would trust.
+#[derive(Debug)]
+pub struct FibbleHelper {
+    max_lies: usize,
+    all_words: Vec<String>,
+    dict_words: Vec<String>,
+}
+
+impl FibbleHelper {
+    pub fn new(width: usize, dict_words: &[String], max_lies: usize) -> FibbleHelper {
+        let all_words: Vec<String> = dict_words.iter()
+            .filter(|word| {
+                word.chars().count() == width
+            })
+            .cloned()
+            .collect();
+        FibbleHelper {
+            max_lies,
+            dict_words: all_words.clone(),
+            all_words,
+        }
+    }
+
+    /// Whether `word` gives hints differing from `hints` of `guess` in at most `max_lies` tiles.
+    pub fn is_consistent(word: &str, guess: &str, hints: &[Hint], max_lies: usize) -> bool {
+        let guess_chars: Vec<char> = guess.chars().collect();
+        let word_chars: Vec<char> = word.chars().collect();
+        if guess_chars.len() != hints.len() || word_chars.len() != hints.len() {
+            return false;
+        }
+        let lies = marks(&guess_chars, &word_chars).iter()
+            .zip(hints)
+            .filter(|(mark, hint)| {
+                let shown = match hint.spot {
+                    Spot::None() => 0,
+                    Spot::InWithout(_) => 1,
+                    Spot::At(_) => 2,
+                };
+                **mark != shown
+            })
+            .count();
+        lies <= max_lies
+    }
+}
+
+impl Helper for FibbleHelper {
+    fn suggest(&self) -> &Vec<String> {
+        &self.dict_words
+    }
+
+    fn add_hint(&mut self, word: &str, hints: &[Hint]) {
+        let max_lies = self.max_lies;
+        // The answer always shows all tiles at the spot, so a guess shown otherwise is not the answer.
+        let solved = Hint::all_at(hints);
+        self.dict_words.retain(|w| (solved || w != word) && Self::is_consistent(w, word, hints, max_lies));
+    }
+
+    fn remained_words_length(&self) -> usize {
+        self.dict_words.len()
+    }
+
+    fn remained_words(&self) -> &Vec<String> {
+        &self.dict_words
+    }
+
+    fn reset(&mut self) {
+        self.dict_words = self.all_words.clone();
+    }
+}
+
+/// Orders guesses by expected information when up to `max_lies` tiles may lie.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct LieEntropy {
+    pub max_lies: usize,
+}
+
+impl LieEntropy {
+    /// Expected information of `guess` when any hints of `answers` differing in
+    /// at most `max_lies` tiles may be shown.
+    ///
+    /// Patterns are weighed as if shown truly, but every answer consistent with
+    /// a pattern within the lies counts as remained after it, so this is the
+    /// entropy of `InformationHelper` without lies and less with more lies.
+    pub(crate) fn entropy(guess: &[char], answers: &[Vec<char>], max_lies: usize) -> f64 {
+        let mut counts: HashMap<Vec<u8>, usize> = HashMap::new();
+        for answer in answers {
+            *counts.entry(marks(guess, answer)).or_insert(0) += 1;
+        }
+        let total = answers.len() as f64;
+        counts.iter()
+            .map(|(pattern, count)| {
+                let remained: usize = counts.iter()
+                    .filter(|(other, _)| {
+                        pattern.iter().zip(other.iter()).filter(|(a, b)| a != b).count() <= max_lies
+                    })
+                    .map(|(_, count)| {
+                        *count
+                    })
+                    .sum();
+                *count as f64 / total * (total / remained as f64).log2()
+            })
+            .sum()
+    }
+}
+
+impl Ranking for LieEntropy {
+    fn rank(&self, guess_words: &[String], answers: &[String]) -> Vec<(String, f64)> {
+        let answer_chars: Vec<Vec<char>> = answers.iter()
+            .map(|word| {
+                word.chars().collect()
+            })
+            .collect();
+        let candidates: HashSet<&String> = answers.iter().collect();
+        let mut scored: Vec<(String, f64, bool)> = guess_words.iter()
+            .map(|word| {
+                let chars: Vec<char> = word.chars().collect();
+                (word.to_string(), Self::entropy(&chars, &answer_chars, self.max_lies), candidates.contains(word))
+            })
+            .collect();
+        scored.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1).unwrap().then(b.2.cmp(&a.2))
+        });
+        scored.into_iter()
+            .map(|(word, score, _)| {
+                (word, score)
+            })
+            .collect()
+    }
+}
+
+/// A helper which narrows answers like `FibbleHelper` and suggests guesses by `LieEntropy`.
+pub type FibbleInformationHelper = RankedHelper<LieEntropy, FibbleHelper>;
+
+impl FibbleInformationHelper {
+    pub fn new(width: usize, dict_words: &[String], max_lies: usize) -> FibbleInformationHelper {
+        Self::with_guesses(width, dict_words, &[], max_lies)
+    }
+
+    pub fn with_guesses(width: usize, answer_words: &[String], guess_words: &[String], max_lies: usize) -> FibbleInformationHelper {
+        Self::filtered(FibbleHelper::new(width, answer_words, max_lies), width, answer_words, guess_words, LieEntropy { max_lies })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::score;
+
+    fn preset_words() -> Vec<String> {
+        vec![
+            "hello".to_string(),
+            "early".to_string(),
+            "asset".to_string(),
+            "bound".to_string(),
+            "heard".to_string(),
+        ]
+    }
+
+    /// Hints of `guess` for `answer` with the tile at `index` turned into a lie.
+    fn lie(guess: &str, answer: &str, index: usize) -> Vec<Hint> {
+        let mut hints = score(guess, answer);
+        hints[index].spot = match hints[index].spot {
+            Spot::At(_) => Spot::None(),
+            _ => Spot::At(index),
+        };
+        hints
+    }
+
+    #[cfg(test)]
+    mod is_consistent {
+        use super::*;
+
+        #[test]
+        fn without_lies() {
+            assert!(FibbleHelper::is_consistent("early", "hello", &score("hello", "early"), 0));
+            assert!(!FibbleHelper::is_consistent("early", "hello", &lie("hello", "early", 0), 0));
+        }
+
+        #[test]
+        fn with_lies() {
+            assert!(FibbleHelper::is_consistent("early", "hello", &lie("hello", "early", 0), 1));
+            assert!(!FibbleHelper::is_consistent("bound", "hello", &score("hello", "early"), 1));
+        }
+
+        #[test]
+        fn invalid_length() {
+            assert!(!FibbleHelper::is_consistent("hell", "hello", &score("hello", "early"), 1));
+        }
+    }
+
+    #[cfg(test)]
+    mod entropy {
+        use super::*;
+        use crate::information::InformationHelper;
+
+        fn answers() -> Vec<Vec<char>> {
+            preset_words().iter()
+                .map(|word| {
+                    word.chars().collect()
+                })
+                .collect()
+        }
+
+        #[test]
+        fn without_lies() {
+            let guess: Vec<char> = "hello".chars().collect();
+            let expected = InformationHelper::entropy(&guess, &answers());
+            assert!((LieEntropy::entropy(&guess, &answers(), 0) - expected).abs() < 1e-9);
+        }
+
+        #[test]
+        fn less_with_lies() {
+            let guess: Vec<char> = "hello".chars().collect();
+            let strict = LieEntropy::entropy(&guess, &answers(), 0);
+            let lying = LieEntropy::entropy(&guess, &answers(), 1);
+            assert!(lying < strict);
+            assert_eq!(LieEntropy::entropy(&guess, &answers(), 5), 0.0);
+        }
+    }
+
+    #[cfg(test)]
+    mod suggest {
+        use super::*;
+
+        #[test]
+        fn keep_answer_after_lie() {
+            let mut target = FibbleHelper::new(5, &preset_words(), 1);
+            target.add_hint("bound", &lie("bound", "early", 2));
+            assert!(target.remained_words().contains(&String::from("early")));
+            assert!(!target.remained_words().contains(&String::from("bound")));
+            assert_eq!(target.suggest(), target.remained_words());
+            assert_eq!(target.scores(), None);
+        }
+
+        #[test]
+        fn strict_without_lies() {
+            let mut target = FibbleHelper::new(5, &preset_words(), 0);
+            target.add_hint("bound", &lie("bound", "early", 2));
+            assert!(!target.remained_words().contains(&String::from("early")));
+        }
+
+        #[test]
+        fn entropy() {
+            let target = FibbleInformationHelper::new(5, &preset_words(), 1);
+            let scores = target.scores().unwrap();
+            assert_eq!(target.suggest().len(), 5);
+            assert!(scores.windows(2).all(|w| w[0] >= w[1]));
+        }
+
+        #[test]
+        fn entropy_keeps_answer_after_lie() {
+            let mut target = FibbleInformationHelper::with_guesses(5, &preset_words(), &["slate".to_string()], 1);
+            target.add_hint("bound", &lie("bound", "early", 2));
+            assert!(target.remained_words().contains(&String::from("early")));
+            assert!(target.suggest().contains(&String::from("slate")));
+            assert!(!target.suggest().contains(&String::from("bound")));
+            target.rebuild(&[]);
+            assert_eq!(target.remained_words_length(), 5);
+        }
+
+        #[test]
+        fn guess_not_solved() {
+            let mut target = FibbleHelper::new(5, &preset_words(), 1);
+            target.add_hint("early", &lie("early", "early", 2));
+            assert!(!target.remained_words().contains(&String::from("early")));
+        }
+
+        #[test]
+        fn rebuild() {
+            let mut target = FibbleHelper::new(5, &preset_words(), 1);
+            target.add_hint("hello", &lie("hello", "early", 1));
+            let remained = target.remained_words().clone();
+            target.rebuild(&[]);
+            assert_eq!(target.remained_words_length(), 5);
+            target.rebuild(&[("hello".to_string(), lie("hello", "early", 1))]);
+            assert_eq!(target.remained_words(), &remained);
+        }
+    }
+}
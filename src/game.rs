@@ -0,0 +1,208 @@
+use std::collections::HashSet;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::{check_hard_mode, Hint, score};
+use crate::absurdle::adversarial_feedback;
+
+/// How to pick the answer of a game from the dictionary.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Pick {
+    Random,
+    Seed(u64),
+    /// Index like a daily puzzle number, wrapped around the number of words.
+    Daily(usize),
+}
+
+pub fn pick_answer(words: &[String], pick: Pick) -> Option<&String> {
+    if words.is_empty() {
+        return None;
+    }
+    let index = match pick {
+        Pick::Random => rand::thread_rng().gen_range(0..words.len()),
+        Pick::Seed(seed) => StdRng::seed_from_u64(seed).gen_range(0..words.len()),
+        Pick::Daily(day) => day % words.len(),
+    };
+    words.get(index)
+}
+
+/// A game hosted locally against a known answer, or against an adversary
+/// which keeps as many candidates as possible like Absurdle.
+#[derive(Debug)]
+pub struct Game {
+    answer: String,
+    /// Answers the adversary may still pick, or empty if the answer is known.
+    candidates: Vec<String>,
+    width: usize,
+    max_guess_count: usize,
+    allowed_words: HashSet<String>,
+    hard_mode: bool,
+    rounds: Vec<(String, Vec<Hint>)>,
+}
+
+impl Game {
+    /// Creates a game which accepts the answer, `answer_words` and `guess_words` as guesses.
+    pub fn new(answer: &str, max_guess_count: usize, answer_words: &[String], guess_words: &[String]) -> Self {
+        let mut allowed_words: HashSet<String> = answer_words.iter().chain(guess_words).cloned().collect();
+        allowed_words.insert(answer.to_string());
+        Game {
+            answer: answer.to_string(),
+            candidates: Vec::new(),
+            width: answer.chars().count(),
+            max_guess_count,
+            allowed_words,
+            hard_mode: false,
+            rounds: Vec::new(),
+        }
+    }
+
+    /// Creates a game whose answer is decided as late as possible among
+    /// `answer_words`, or `None` if there are no answer words.
+    pub fn adversarial(answer_words: &[String], max_guess_count: usize, guess_words: &[String]) -> Option<Self> {
+        let mut game = Self::new(answer_words.first()?, max_guess_count, answer_words, guess_words);
+        game.candidates = answer_words.to_vec();
+        Some(game)
+    }
+
+    pub fn with_hard_mode(mut self, hard_mode: bool) -> Self {
+        self.hard_mode = hard_mode;
+        self
+    }
+
+    /// Scores `word` against the answer, or tells why it is not accepted as a guess.
+    pub fn guess(&mut self, word: &str) -> Result<Vec<Hint>, String> {
+        if self.is_over() {
+            return Err(String::from("game is over"));
+        }
+        if word.chars().count() != self.width {
+            return Err(String::from("invalid length"));
+        }
+        if !self.allowed_words.contains(word) {
+            return Err(String::from("not in word list"));
+        }
+        if self.hard_mode {
+            for (_, hints) in &self.rounds {
+                check_hard_mode(word, hints)?;
+            }
+        }
+        let hints = match self.candidates.is_empty() {
+            true => score(word, &self.answer),
+            false => {
+                let hints = adversarial_feedback(word, &self.candidates).unwrap();
+                self.candidates.retain(|candidate| score(word, candidate) == hints);
+                self.answer = self.candidates[0].to_string();
+                hints
+            }
+        };
+        self.rounds.push((word.to_string(), hints.clone()));
+        Ok(hints)
+    }
+
+    /// The answer, or one of the answers the adversary may still pick.
+    pub fn answer(&self) -> &str {
+        &self.answer
+    }
+
+    pub fn rounds(&self) -> &Vec<(String, Vec<Hint>)> {
+        &self.rounds
+    }
+
+    pub fn is_solved(&self) -> bool {
+        matches!(self.rounds.last(), Some((_, hints)) if Hint::all_at(hints))
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.is_solved() || self.rounds.len() >= self.max_guess_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preset_words() -> Vec<String> {
+        vec!["hello".to_string(), "early".to_string(), "asset".to_string()]
+    }
+
+    #[cfg(test)]
+    mod pick_answer {
+        use super::*;
+
+        #[test]
+        fn daily() {
+            assert_eq!(pick_answer(&preset_words(), Pick::Daily(1)), Some(&String::from("early")));
+            assert_eq!(pick_answer(&preset_words(), Pick::Daily(5)), Some(&String::from("asset")));
+        }
+
+        #[test]
+        fn seed() {
+            let words = preset_words();
+            let actual = pick_answer(&words, Pick::Seed(42));
+            assert!(actual.is_some());
+            assert_eq!(actual, pick_answer(&words, Pick::Seed(42)));
+        }
+
+        #[test]
+        fn empty() {
+            assert_eq!(pick_answer(&[], Pick::Random), None);
+        }
+    }
+
+    #[cfg(test)]
+    mod guess {
+        use super::*;
+
+        #[test]
+        fn solved() {
+            let mut game = Game::new("early", 6, &preset_words(), &[]);
+            assert_eq!(game.guess("hello"), Ok(score("hello", "early")));
+            assert!(!game.is_over());
+            assert_eq!(game.guess("early"), Ok(score("early", "early")));
+            assert!(game.is_solved());
+            assert!(game.is_over());
+            assert_eq!(game.guess("asset"), Err(String::from("game is over")));
+            assert_eq!(game.rounds().len(), 2);
+        }
+
+        #[test]
+        fn failed() {
+            let mut game = Game::new("early", 1, &preset_words(), &[]);
+            game.guess("hello").unwrap();
+            assert!(!game.is_solved());
+            assert!(game.is_over());
+        }
+
+        #[test]
+        fn invalid() {
+            let mut game = Game::new("early", 6, &preset_words(), &[]);
+            assert_eq!(game.guess("hell"), Err(String::from("invalid length")));
+            assert_eq!(game.guess("zzzzz"), Err(String::from("not in word list")));
+            assert!(game.rounds().is_empty());
+        }
+
+        #[test]
+        fn accept_dictionary_words() {
+            let mut game = Game::new("early", 6, &["early".to_string(), "asset".to_string()], &["heard".to_string()]);
+            assert_eq!(game.guess("asset"), Ok(score("asset", "early")));
+            assert_eq!(game.guess("heard"), Ok(score("heard", "early")));
+        }
+
+        #[test]
+        fn adversarial() {
+            let mut game = Game::adversarial(&preset_words(), 6, &[]).unwrap();
+            assert_eq!(game.guess("hello"), Ok(score("hello", "asset")));
+            assert_eq!(game.answer(), "asset");
+            assert_eq!(game.guess("asset"), Ok(score("asset", "asset")));
+            assert!(game.is_solved());
+            assert!(Game::adversarial(&[], 6, &[]).is_none());
+        }
+
+        #[test]
+        fn hard_mode() {
+            let mut game = Game::new("early", 6, &preset_words(), &[]).with_hard_mode(true);
+            game.guess("hello").unwrap();
+            assert_eq!(game.guess("asset"), Err(String::from("4th letter must be L")));
+        }
+    }
+}
@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+
+use crate::{marks, Weights};
+use crate::opening::Opening;
+use crate::ranked::{RankedHelper, Ranking};
+
+/// Step of expected information below which guesses are taken as a tie.
+const ENTROPY_TOLERANCE: f64 = 1e-9;
+
+/// Counts `answers` by the feedback they would give to `guess`.
+pub(crate) fn buckets(guess: &[char], answers: &[Vec<char>]) -> HashMap<u64, usize> {
+    let mut buckets: HashMap<u64, usize> = HashMap::new();
+    for answer in answers {
+        *buckets.entry(InformationHelper::feedback(guess, answer)).or_insert(0) += 1;
+    }
+    buckets
+}
+
+/// Orders guesses by expected information over remained answers, each as
+/// likely as its weight.
+#[derive(Debug, Default)]
+pub struct Entropy {
+    weights: Weights,
+}
+
+impl Entropy {
+    fn weight(&self, word: &str) -> f64 {
+        self.weights.get(word).copied().unwrap_or(1.0)
+    }
+}
+
+impl Ranking for Entropy {
+    fn rank(&self, guess_words: &[String], answers: &[String]) -> Vec<(String, f64)> {
+        let answer_chars: Vec<Vec<char>> = answers.iter()
+            .map(|word| {
+                word.chars().collect()
+            })
+            .collect();
+        let weights: Vec<f64> = answers.iter()
+            .map(|word| {
+                self.weight(word)
+            })
+            .collect();
+        let total: f64 = weights.iter().sum();
+        let probabilities: HashMap<&String, f64> = answers.iter()
+            .zip(&weights)
+            .map(|(word, weight)| {
+                (word, match total > 0.0 {
+                    true => weight / total,
+                    false => 0.0,
+                })
+            })
+            .collect();
+        // Guesses as informative as each other are ordered by the chance to win at once.
+        let mut scored: Vec<(String, f64, f64)> = guess_words.iter()
+            .map(|word| {
+                let chars: Vec<char> = word.chars().collect();
+                let probability = probabilities.get(word).copied().unwrap_or(0.0);
+                (word.to_string(), InformationHelper::weighted_entropy(&chars, &answer_chars, &weights), probability)
+            })
+            .collect();
+        // Sums of the same buckets in a different order may differ in the last bits,
+        // so entropy is compared in steps of the tolerance to keep the order total.
+        let step = |entropy: f64| {
+            (entropy / ENTROPY_TOLERANCE).round() as i64
+        };
+        scored.sort_by(|a, b| {
+            step(b.1).cmp(&step(a.1)).then(b.2.partial_cmp(&a.2).unwrap())
+        });
+        scored.into_iter()
+            .map(|(word, score, _)| {
+                (word, score)
+            })
+            .collect()
+    }
+
+    fn probability(&self, word: &str, answers: &[String]) -> Option<f64> {
+        if self.weights.is_empty() {
+            return None;
+        }
+        if !answers.iter().any(|w| w == word) {
+            return Some(0.0);
+        }
+        let total: f64 = answers.iter()
+            .map(|w| {
+                self.weight(w)
+            })
+            .sum();
+        match total > 0.0 {
+            true => Some(self.weight(word) / total),
+            false => Some(0.0),
+        }
+    }
+}
+
+/// A helper which suggests guesses by expected information gain in bits.
+pub type InformationHelper = RankedHelper<Entropy>;
+
+impl InformationHelper {
+    pub fn new(width: usize, dict_words: &[String]) -> InformationHelper {
+        Self::with_guesses(width, dict_words, &[])
+    }
+
+    pub fn with_guesses(width: usize, answer_words: &[String], guess_words: &[String]) -> InformationHelper {
+        Self::ranked(width, answer_words, guess_words, Entropy::default())
+    }
+
+    /// Weighs remained words by `weights` instead of taking them as equally likely.
+    pub fn with_weights(width: usize, answer_words: &[String], guess_words: &[String], weights: Weights) -> InformationHelper {
+        Self::ranked(width, answer_words, guess_words, Entropy { weights })
+    }
+
+    /// The `opening` must be ranked for the same `weights`.
+    pub fn with_opening(width: usize, answer_words: &[String], guess_words: &[String], weights: Weights, opening: Opening) -> InformationHelper {
+        Self::opened(width, answer_words, guess_words, Entropy { weights }, opening)
+    }
+
+    fn feedback(guess: &[char], answer: &[char]) -> u64 {
+        marks(guess, answer).iter().fold(0, |acc, m| acc * 3 + *m as u64)
+    }
+
+    pub(crate) fn entropy(guess: &[char], answers: &[Vec<char>]) -> f64 {
+        let total = answers.len() as f64;
+        buckets(guess, answers).values()
+            .map(|count| {
+                let p = *count as f64 / total;
+                -p * p.log2()
+            })
+            .sum()
+    }
+
+    /// Expected information of `guess` when each of `answers` is as likely as its weight in `weights`.
+    pub(crate) fn weighted_entropy(guess: &[char], answers: &[Vec<char>], weights: &[f64]) -> f64 {
+        let mut buckets: HashMap<u64, f64> = HashMap::new();
+        for (answer, weight) in answers.iter().zip(weights) {
+            *buckets.entry(Self::feedback(guess, answer)).or_insert(0.0) += weight;
+        }
+        let total: f64 = weights.iter().sum();
+        buckets.values()
+            .map(|weight| {
+                weight / total
+            })
+            .filter(|p| {
+                *p > 0.0
+            })
+            .map(|p| {
+                -p * p.log2()
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Helper, Hint, score, Spot};
+
+    #[cfg(test)]
+    mod feedback {
+        use super::*;
+
+        fn feedback(guess: &str, answer: &str) -> u64 {
+            InformationHelper::feedback(
+                &guess.chars().collect::<Vec<char>>(),
+                &answer.chars().collect::<Vec<char>>(),
+            )
+        }
+
+        #[test]
+        fn all_at() {
+            assert_eq!(feedback("early", "early"), 242);
+        }
+
+        #[test]
+        fn nowhere() {
+            assert_eq!(feedback("bound", "early"), 0);
+        }
+
+        #[test]
+        fn same_pattern_same_code() {
+            assert_eq!(feedback("hello", "early"), feedback("hello", "apple"));
+            assert_ne!(feedback("hello", "early"), feedback("hello", "asset"));
+        }
+    }
+
+    #[cfg(test)]
+    mod suggest {
+        use super::*;
+
+        fn preset_words() -> Vec<String> {
+            vec![
+                "hello".to_string(),
+                "early".to_string(),
+                "asset".to_string(),
+                "bound".to_string(),
+                "heard".to_string(),
+            ]
+        }
+
+        #[test]
+        fn sorted_by_score() {
+            let target = InformationHelper::new(5, &preset_words());
+            let scores = target.scores().unwrap();
+            assert_eq!(target.suggest().len(), 5);
+            assert_eq!(scores.len(), 5);
+            assert!(scores.windows(2).all(|w| w[0] >= w[1]));
+        }
+
+        #[test]
+        fn max_score_splits_all() {
+            let target = InformationHelper::new(5, &preset_words());
+            assert_eq!(target.suggest()[0], "hello");
+            assert!((target.scores().unwrap()[0] - 5f64.log2()).abs() < 1e-9);
+        }
+
+        #[test]
+        fn rerank_after_hint() {
+            let mut target = InformationHelper::new(5, &preset_words());
+            target.add_hint("bound", &[Hint::new('b', Spot::None()),
+                Hint::new('o', Spot::None()),
+                Hint::new('u', Spot::None()),
+                Hint::new('n', Spot::None()),
+                Hint::new('d', Spot::None())]);
+            assert_eq!(target.remained_words_length(), 2);
+            assert_eq!(target.suggest(), &vec![
+                String::from("early"),
+                String::from("asset"),
+                String::from("hello"),
+                String::from("heard"),
+            ]);
+            assert_eq!(target.scores().unwrap(), &vec![1.0, 1.0, 1.0, 1.0]);
+        }
+
+        #[test]
+        fn probe_without_hard_mode() {
+            let mut target = InformationHelper::new(5, &preset_words());
+            target.add_hint("heard", &score("heard", "early"));
+            assert_eq!(target.remained_words_length(), 1);
+            assert_eq!(target.suggest(), &vec![
+                String::from("early"),
+                String::from("hello"),
+                String::from("asset"),
+                String::from("bound"),
+            ]);
+        }
+
+        #[test]
+        fn with_guesses() {
+            let target = InformationHelper::with_guesses(
+                5,
+                &["early".to_string(), "asset".to_string()],
+                &["heard".to_string(), "early".to_string(), "zo".to_string()],
+            );
+            assert_eq!(target.remained_words_length(), 2);
+            assert_eq!(target.suggest(), &vec![
+                String::from("early"),
+                String::from("asset"),
+                String::from("heard"),
+            ]);
+        }
+
+        #[test]
+        fn with_opening() {
+            let ranked = InformationHelper::new(5, &preset_words());
+            let opening = Opening::new(5, "entropy", "0", ranked.suggest(), ranked.scores().unwrap());
+            let mut target = InformationHelper::with_opening(5, &preset_words(), &[], Weights::new(), opening);
+            assert_eq!(target.suggest(), ranked.suggest());
+            assert_eq!(target.scores(), ranked.scores());
+            target.add_hint("heard", &score("heard", "early"));
+            target.reset();
+            assert_eq!(target.suggest(), ranked.suggest());
+        }
+
+        #[test]
+        fn with_weights() {
+            let weights: Weights = [("early", 8.0), ("asset", 1.0), ("bound", 1.0)].iter()
+                .map(|(word, weight)| {
+                    (word.to_string(), *weight)
+                })
+                .collect();
+            let mut target = InformationHelper::with_weights(5, &preset_words(), &[], weights);
+            // 12 in total, with "hello" and "heard" weighted 1 as they are not given.
+            assert_eq!(target.probability("early"), Some(8.0 / 12.0));
+            assert_eq!(target.probability("zzzzz"), Some(0.0));
+            let p: [f64; 5] = [8.0 / 12.0, 1.0 / 12.0, 1.0 / 12.0, 1.0 / 12.0, 1.0 / 12.0];
+            let expected: f64 = p.iter().map(|p| -p * p.log2()).sum();
+            assert!((target.scores().unwrap()[0] - expected).abs() < 1e-9);
+            // "early" is the likeliest of guesses splitting all words.
+            assert_eq!(target.suggest()[0], "early");
+            target.add_hint("bound", &score("bound", "early"));
+            assert_eq!(target.probability("early"), Some(8.0 / 9.0));
+        }
+
+        #[test]
+        fn without_weights() {
+            let target = InformationHelper::with_weights(5, &preset_words(), &[], Weights::new());
+            assert_eq!(target.probability("early"), None);
+            assert_eq!(target.suggest()[0], "hello");
+        }
+
+        #[test]
+        fn rebuild() {
+            let mut target = InformationHelper::new(5, &preset_words()).with_hard_mode(true);
+            let initial = target.suggest().clone();
+            target.add_hint("heard", &score("heard", "asset"));
+            target.rebuild(&[]);
+            assert_eq!(target.suggest(), &initial);
+            assert_eq!(target.remained_words_length(), 5);
+            target.rebuild(&[("heard".to_string(), score("heard", "early"))]);
+            assert_eq!(target.suggest(), &vec![String::from("early")]);
+        }
+
+        #[test]
+        fn hard_mode() {
+            let mut target = InformationHelper::new(5, &preset_words()).with_hard_mode(true);
+            target.add_hint("heard", &score("heard", "early"));
+            assert_eq!(target.suggest(), &vec![String::from("early")]);
+            assert_eq!(target.scores().unwrap(), &vec![0.0]);
+        }
+    }
+}
@@ -1,4 +1,21 @@
+use std::collections::{HashMap, HashSet};
+use std::iter::zip;
+
+pub mod absurdle;
+pub mod bench;
+pub mod diagnosis;
+pub mod error;
+pub mod fibble;
+pub mod game;
+pub mod information;
+pub mod minimax;
+pub mod multi;
+pub mod opening;
+pub mod ranked;
+pub mod session;
+pub mod share;
 pub mod simple;
+pub mod tree;
 pub mod txt;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -25,16 +42,180 @@ impl Hint {
             })
             .count() == hints.len()
     }
+    /// Parses hint digits for `word` as they are input; `0` for nowhere, `1` for somewhere and `2` for just.
+    pub fn from_pattern(word: &str, pattern: &str) -> Result<Vec<Hint>, &'static str> {
+        if word.chars().count() != pattern.chars().count() {
+            return Err("invalid length");
+        }
+        zip(word.chars(), pattern.chars())
+            .enumerate()
+            .map(|(i, (word_c, hint_c))| {
+                match hint_c {
+                    '0' => Ok(Hint::new(word_c, Spot::None())),
+                    '1' => Ok(Hint::new(word_c, Spot::InWithout(i))),
+                    '2' => Ok(Hint::new(word_c, Spot::At(i))),
+                    _ => Err("input must be 0,1,2"),
+                }
+            })
+            .collect()
+    }
+    /// Formats hints as digits as they are input; `0` for `None()`, `1` for `InWithout` and `2` for `At`.
+    pub fn pattern(hints: &[Hint]) -> String {
+        hints.iter()
+            .map(|h| {
+                match h.spot {
+                    Spot::None() => '0',
+                    Spot::InWithout(_) => '1',
+                    Spot::At(_) => '2',
+                }
+            })
+            .collect()
+    }
+}
+
+/// Computes the hints Wordle would show for `guess` when the answer is `answer`.
+///
+/// Exact matches are marked first, then each remaining letter is marked as
+/// `InWithout` only while unmatched occurrences are left in the answer, so
+/// surplus duplicates of a letter become `None()`.
+pub fn score(guess: &str, answer: &str) -> Vec<Hint> {
+    let guess: Vec<char> = guess.chars().collect();
+    let answer: Vec<char> = answer.chars().collect();
+    zip(&guess, marks(&guess, &answer))
+        .enumerate()
+        .map(|(i, (c, mark))| {
+            match mark {
+                2 => Hint::new(*c, Spot::At(i)),
+                1 => Hint::new(*c, Spot::InWithout(i)),
+                _ => Hint::new(*c, Spot::None()),
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn marks(guess: &[char], answer: &[char]) -> Vec<u8> {
+    let mut marks = vec![0u8; guess.len()];
+    let mut used = vec![false; answer.len()];
+    for (i, (g, a)) in zip(guess, answer).enumerate() {
+        if g == a {
+            marks[i] = 2;
+            used[i] = true;
+        }
+    }
+    for (i, g) in guess.iter().enumerate() {
+        if marks[i] == 2 {
+            continue;
+        }
+        if let Some(j) = (0..answer.len()).find(|j| !used[*j] && answer[*j] == *g) {
+            marks[i] = 1;
+            used[j] = true;
+        }
+    }
+    marks
+}
+
+/// Checks whether `word` may be guessed in hard mode after a round of `hints`.
+///
+/// Every `At` letter must be reused in place and every revealed letter must
+/// be included at least as many times as it was marked.
+pub fn check_hard_mode(word: &str, hints: &[Hint]) -> Result<(), String> {
+    let chars: Vec<char> = word.chars().collect();
+    for hint in hints {
+        if let Spot::At(at) = hint.spot {
+            if chars.get(at) != Some(&hint.letter) {
+                return Err(format!("{} letter must be {}", ordinal(at + 1), hint.letter.to_uppercase()));
+            }
+        }
+    }
+    for hint in hints {
+        if hint.spot == Spot::None() {
+            continue;
+        }
+        let required = hints.iter()
+            .filter(|h| {
+                h.letter == hint.letter && h.spot != Spot::None()
+            })
+            .count();
+        if chars.iter().filter(|c| **c == hint.letter).count() < required {
+            return Err(format!("guess must contain {}", hint.letter.to_uppercase()));
+        }
+    }
+    Ok(())
+}
+
+/// Words a helper may suggest: `answer_words` of `width` followed by `guess_words`
+/// of `width` which are not answers, each guess only once.
+pub(crate) fn guess_pool(width: usize, answer_words: &[String], guess_words: &[String]) -> Vec<String> {
+    let mut pool: Vec<String> = answer_words.iter()
+        .filter(|word| {
+            word.chars().count() == width
+        })
+        .cloned()
+        .collect();
+    let mut seen: HashSet<String> = pool.iter().cloned().collect();
+    for word in guess_words {
+        if word.chars().count() == width && seen.insert(word.to_string()) {
+            pool.push(word.to_string());
+        }
+    }
+    pool
+}
+
+pub(crate) fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
 }
 
 pub trait Helper {
     fn suggest(&self) -> &Vec<String>;
+    /// Narrows the words with `hints` of `word`, where `hints[i]` is of the `i`th letter.
+    ///
+    /// A `Spot::None()` hint has no position of its own, so it tells the letter
+    /// is not at its index in `hints`.
     fn add_hint(&mut self, word: &str, hints: &[Hint]);
     fn remained_words_length(&self) -> usize;
+    fn remained_words(&self) -> &Vec<String>;
+    /// Forgets all hints and starts again from the initial words.
+    fn reset(&mut self);
+    /// Recomputes from the initial words with `rounds` of guesses and hints,
+    /// e.g. after a mistyped round is corrected.
+    fn rebuild(&mut self, rounds: &[(String, Vec<Hint>)]) {
+        self.reset();
+        for (word, hints) in rounds {
+            self.add_hint(word, hints);
+        }
+    }
+    fn scores(&self) -> Option<&Vec<f64>> {
+        None
+    }
+    /// Probability that `word` is the answer among remained words, if the helper weighs words.
+    fn probability(&self, _word: &str) -> Option<f64> {
+        None
+    }
 }
 
+/// Relative likelihood of words being the answer, such as how often they are used.
+///
+/// Words without weights are as likely as words weighted 1.
+pub type Weights = HashMap<String, f64>;
+
 pub trait Dictionary {
-    fn extract_words(&self, word_length: usize) -> Vec<String>;
+    /// Extracts words which may be the answer, or `Error::Empty` if there are none.
+    fn extract_words(&self, word_length: usize) -> Result<Vec<String>, error::Error>;
+    /// Extracts words which are allowed as a guess in addition to the answers.
+    fn extract_guesses(&self, _word_length: usize) -> Result<Vec<String>, error::Error> {
+        Ok(Vec::new())
+    }
+    /// Extracts weights of the answers, which are empty if all of them are as likely.
+    fn extract_weights(&self, _word_length: usize) -> Result<Weights, error::Error> {
+        Ok(Weights::new())
+    }
 }
 
 #[cfg(test)]
@@ -62,4 +243,107 @@ mod tests {
             Hint::new('y', Spot::None()),
         ]))
     }
+
+    #[test]
+    fn from_pattern() {
+        assert_eq!(Hint::from_pattern("solve", "02111"), Ok(score("solve", "lover")));
+        assert_eq!(Hint::from_pattern("solve", "0211"), Err("invalid length"));
+        assert_eq!(Hint::from_pattern("solve", "0211a"), Err("input must be 0,1,2"));
+    }
+
+    #[test]
+    fn pattern() {
+        assert_eq!(Hint::pattern(&score("solve", "lover")), "02111");
+        assert_eq!(Hint::pattern(&score("early", "early")), "22222");
+    }
+
+    #[cfg(test)]
+    mod check_hard_mode {
+        use super::*;
+
+        #[test]
+        fn valid() {
+            assert!(check_hard_mode("early", &score("heard", "early")).is_ok());
+            assert!(check_hard_mode("apple", &score("bound", "early")).is_ok());
+        }
+
+        #[test]
+        fn at_not_reused() {
+            assert_eq!(check_hard_mode("hello", &score("solve", "lover")),
+                       Err(String::from("2nd letter must be O")));
+        }
+
+        #[test]
+        fn in_without_not_included() {
+            assert_eq!(check_hard_mode("asset", &score("heard", "early")),
+                       Err(String::from("guess must contain R")));
+        }
+
+        #[test]
+        fn duplicate_required() {
+            assert_eq!(check_hard_mode("lapse", &score("hello", "llama")),
+                       Err(String::from("guess must contain L")));
+            assert!(check_hard_mode("lilac", &score("hello", "llama")).is_ok());
+        }
+    }
+
+    #[cfg(test)]
+    mod score {
+        use super::*;
+
+        #[test]
+        fn all_at() {
+            assert_eq!(score("early", "early"), vec![
+                Hint::new('e', Spot::At(0)),
+                Hint::new('a', Spot::At(1)),
+                Hint::new('r', Spot::At(2)),
+                Hint::new('l', Spot::At(3)),
+                Hint::new('y', Spot::At(4)),
+            ])
+        }
+
+        #[test]
+        fn mixed() {
+            assert_eq!(score("solve", "lover"), vec![
+                Hint::new('s', Spot::None()),
+                Hint::new('o', Spot::At(1)),
+                Hint::new('l', Spot::InWithout(2)),
+                Hint::new('v', Spot::InWithout(3)),
+                Hint::new('e', Spot::InWithout(4)),
+            ])
+        }
+
+        #[test]
+        fn duplicate_after_at() {
+            assert_eq!(score("geese", "those"), vec![
+                Hint::new('g', Spot::None()),
+                Hint::new('e', Spot::None()),
+                Hint::new('e', Spot::None()),
+                Hint::new('s', Spot::At(3)),
+                Hint::new('e', Spot::At(4)),
+            ])
+        }
+
+        #[test]
+        fn duplicate_only_first_in_without() {
+            assert_eq!(score("speed", "abide"), vec![
+                Hint::new('s', Spot::None()),
+                Hint::new('p', Spot::None()),
+                Hint::new('e', Spot::InWithout(2)),
+                Hint::new('e', Spot::None()),
+                Hint::new('d', Spot::InWithout(4)),
+            ])
+        }
+
+        #[test]
+        fn japanese() {
+            assert_eq!(score("バタフリー", "バクフーン"), vec![
+                Hint::new('バ', Spot::At(0)),
+                Hint::new('タ', Spot::None()),
+                Hint::new('フ', Spot::At(2)),
+                Hint::new('リ', Spot::None()),
+                Hint::new('ー', Spot::InWithout(4)),
+            ])
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,182 @@
+use std::collections::HashSet;
+
+use crate::information::buckets;
+use crate::opening::Opening;
+use crate::ranked::{RankedHelper, Ranking};
+
+/// How to measure the remained words after a guess, lower is better.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Criterion {
+    /// Number of remained words in the largest bucket.
+    WorstCase,
+    /// Number of remained words on average.
+    ExpectedSize,
+}
+
+impl Ranking for Criterion {
+    fn rank(&self, guess_words: &[String], answers: &[String]) -> Vec<(String, f64)> {
+        let answer_chars: Vec<Vec<char>> = answers.iter()
+            .map(|word| {
+                word.chars().collect()
+            })
+            .collect();
+        let candidates: HashSet<&String> = answers.iter().collect();
+        let mut scored: Vec<(String, f64, bool)> = guess_words.iter()
+            .map(|word| {
+                let chars: Vec<char> = word.chars().collect();
+                (word.to_string(), MinimaxHelper::measure(*self, &chars, &answer_chars), candidates.contains(word))
+            })
+            .collect();
+        scored.sort_by(|a, b| {
+            a.1.partial_cmp(&b.1).unwrap().then(b.2.cmp(&a.2))
+        });
+        scored.into_iter()
+            .map(|(word, score, _)| {
+                (word, score)
+            })
+            .collect()
+    }
+}
+
+/// A helper which suggests guesses by the remained words after them, measured by `Criterion`.
+pub type MinimaxHelper = RankedHelper<Criterion>;
+
+impl MinimaxHelper {
+    pub fn new(width: usize, dict_words: &[String], criterion: Criterion) -> MinimaxHelper {
+        Self::with_guesses(width, dict_words, &[], criterion)
+    }
+
+    pub fn with_guesses(width: usize, answer_words: &[String], guess_words: &[String], criterion: Criterion) -> MinimaxHelper {
+        Self::ranked(width, answer_words, guess_words, criterion)
+    }
+
+    pub fn with_opening(width: usize, answer_words: &[String], guess_words: &[String], criterion: Criterion, opening: Opening) -> MinimaxHelper {
+        Self::opened(width, answer_words, guess_words, criterion, opening)
+    }
+
+    fn measure(criterion: Criterion, guess: &[char], answers: &[Vec<char>]) -> f64 {
+        let buckets = buckets(guess, answers);
+        match criterion {
+            Criterion::WorstCase => buckets.values().max().map_or(0.0, |count| *count as f64),
+            Criterion::ExpectedSize => {
+                let total = answers.len() as f64;
+                buckets.values()
+                    .map(|count| {
+                        (*count * *count) as f64 / total
+                    })
+                    .sum()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Helper, Hint, score, Spot};
+
+    #[cfg(test)]
+    mod measure {
+        use super::*;
+
+        fn measure(criterion: Criterion, guess: &str, answers: &[&str]) -> f64 {
+            let answers: Vec<Vec<char>> = answers.iter()
+                .map(|answer| {
+                    answer.chars().collect()
+                })
+                .collect();
+            MinimaxHelper::measure(criterion, &guess.chars().collect::<Vec<char>>(), &answers)
+        }
+
+        #[test]
+        fn worst_case() {
+            assert_eq!(measure(Criterion::WorstCase, "hello", &["early", "apple", "asset"]), 2.0);
+        }
+
+        #[test]
+        fn expected_size() {
+            let actual = measure(Criterion::ExpectedSize, "hello", &["early", "apple", "asset"]);
+            assert!((actual - 5.0 / 3.0).abs() < 1e-9);
+        }
+
+        #[test]
+        fn no_answers() {
+            assert_eq!(measure(Criterion::WorstCase, "hello", &[]), 0.0);
+            assert_eq!(measure(Criterion::ExpectedSize, "hello", &[]), 0.0);
+        }
+    }
+
+    #[cfg(test)]
+    mod suggest {
+        use super::*;
+
+        fn preset_words() -> Vec<String> {
+            vec![
+                "hello".to_string(),
+                "early".to_string(),
+                "asset".to_string(),
+                "bound".to_string(),
+                "heard".to_string(),
+            ]
+        }
+
+        #[test]
+        fn sorted_by_score() {
+            for criterion in [Criterion::WorstCase, Criterion::ExpectedSize] {
+                let target = MinimaxHelper::new(5, &preset_words(), criterion);
+                let scores = target.scores().unwrap();
+                assert_eq!(target.suggest().len(), 5);
+                assert_eq!(scores.len(), 5);
+                assert!(scores.windows(2).all(|w| w[0] <= w[1]));
+                assert_eq!(target.suggest()[0], "hello");
+                assert_eq!(scores[0], 1.0);
+            }
+        }
+
+        #[test]
+        fn rerank_after_hint() {
+            let mut target = MinimaxHelper::new(5, &preset_words(), Criterion::WorstCase);
+            target.add_hint("bound", &[Hint::new('b', Spot::None()),
+                Hint::new('o', Spot::None()),
+                Hint::new('u', Spot::None()),
+                Hint::new('n', Spot::None()),
+                Hint::new('d', Spot::None())]);
+            assert_eq!(target.remained_words_length(), 2);
+            assert_eq!(target.suggest(), &vec![
+                String::from("early"),
+                String::from("asset"),
+                String::from("hello"),
+                String::from("heard"),
+            ]);
+            assert_eq!(target.scores().unwrap(), &vec![1.0, 1.0, 1.0, 1.0]);
+        }
+
+        #[test]
+        fn with_guesses() {
+            let target = MinimaxHelper::with_guesses(
+                5,
+                &["early".to_string(), "asset".to_string()],
+                &["heard".to_string(), "early".to_string(), "zo".to_string()],
+                Criterion::ExpectedSize,
+            );
+            assert_eq!(target.remained_words_length(), 2);
+            assert_eq!(target.suggest(), &vec![
+                String::from("early"),
+                String::from("asset"),
+                String::from("heard"),
+            ]);
+        }
+
+        #[test]
+        fn rebuild() {
+            let mut target = MinimaxHelper::new(5, &preset_words(), Criterion::WorstCase).with_hard_mode(true);
+            let initial = target.suggest().clone();
+            target.add_hint("heard", &score("heard", "asset"));
+            target.rebuild(&[]);
+            assert_eq!(target.suggest(), &initial);
+            assert_eq!(target.remained_words_length(), 5);
+            target.rebuild(&[("heard".to_string(), score("heard", "early"))]);
+            assert_eq!(target.suggest(), &vec![String::from("early")]);
+        }
+    }
+}
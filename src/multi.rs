@@ -0,0 +1,208 @@
+use std::collections::HashSet;
+
+use crate::{guess_pool, Hint, Helper};
+use crate::information::InformationHelper;
+use crate::simple::SimpleHelper;
+
+/// Remained words shared by `count` unsolved boards.
+struct AnswerSet<'a> {
+    answers: Vec<Vec<char>>,
+    candidates: HashSet<&'a String>,
+    count: usize,
+}
+
+/// A helper for variants like Dordle, Quordle and Octordle, where each guess
+/// is shared across boards with independent answers.
+#[derive(Debug)]
+pub struct MultiHelper {
+    boards: Vec<SimpleHelper>,
+    solved: Vec<bool>,
+    all_guess_words: Vec<String>,
+    guess_words: Vec<String>,
+    ranked_words: Vec<String>,
+    scores: Vec<f64>,
+}
+
+impl MultiHelper {
+    pub fn new(width: usize, dict_words: &[String], board_count: usize) -> MultiHelper {
+        Self::with_guesses(width, dict_words, &[], board_count)
+    }
+
+    /// Narrows `answer_words` on every board.
+    pub fn with_guesses(width: usize, answer_words: &[String], guess_words: &[String], board_count: usize) -> MultiHelper {
+        let board = SimpleHelper::new(width, answer_words);
+        let all_guess_words = guess_pool(width, answer_words, guess_words);
+        let mut helper = MultiHelper {
+            boards: vec![board; board_count],
+            solved: vec![false; board_count],
+            guess_words: all_guess_words.clone(),
+            all_guess_words,
+            ranked_words: Vec::new(),
+            scores: Vec::new(),
+        };
+        helper.rank();
+        helper
+    }
+
+    /// Narrows every board by its hints of `word`, in the order of boards.
+    ///
+    /// Hints of boards already solved are ignored, so they may be empty.
+    pub fn add_hints(&mut self, word: &str, hints: &[Vec<Hint>]) {
+        for (i, board_hints) in hints.iter().enumerate().take(self.boards.len()) {
+            if self.solved[i] {
+                continue;
+            }
+            if Hint::all_at(board_hints) {
+                self.solved[i] = true;
+            } else {
+                self.boards[i].add_hint(word, board_hints);
+            }
+        }
+        self.guess_words.retain(|w| w != word);
+        self.rank();
+    }
+
+    /// Ranks guesses by the sum of expected information over unsolved boards.
+    fn rank(&mut self) {
+        // Boards often share the same remained words, especially before the first guess.
+        let mut answer_sets: Vec<(&Vec<String>, usize)> = Vec::new();
+        for (board, _) in self.boards.iter().zip(&self.solved).filter(|(_, solved)| !**solved) {
+            match answer_sets.iter_mut().find(|(words, _)| *words == board.remained_words()) {
+                Some((_, count)) => *count += 1,
+                None => answer_sets.push((board.remained_words(), 1)),
+            }
+        }
+        let answer_sets: Vec<AnswerSet> = answer_sets.into_iter()
+            .map(|(words, count)| {
+                AnswerSet {
+                    answers: words.iter()
+                        .map(|word| {
+                            word.chars().collect()
+                        })
+                        .collect(),
+                    candidates: words.iter().collect(),
+                    count,
+                }
+            })
+            .collect();
+        let mut scored: Vec<(String, f64, usize)> = self.guess_words.iter()
+            .map(|word| {
+                let chars: Vec<char> = word.chars().collect();
+                let mut score = 0.0;
+                let mut candidate_count = 0;
+                for set in &answer_sets {
+                    score += InformationHelper::entropy(&chars, &set.answers) * set.count as f64;
+                    if set.candidates.contains(word) {
+                        candidate_count += set.count;
+                    }
+                }
+                (word.to_string(), score, candidate_count)
+            })
+            .collect();
+        scored.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1).unwrap().then(b.2.cmp(&a.2))
+        });
+        let (ranked_words, scores) = scored.into_iter()
+            .map(|(word, score, _)| {
+                (word, score)
+            })
+            .unzip();
+        self.ranked_words = ranked_words;
+        self.scores = scores;
+    }
+
+    pub fn suggest(&self) -> &Vec<String> {
+        &self.ranked_words
+    }
+
+    pub fn scores(&self) -> &Vec<f64> {
+        &self.scores
+    }
+
+    pub fn board_count(&self) -> usize {
+        self.boards.len()
+    }
+
+    pub fn is_solved(&self, board: usize) -> bool {
+        self.solved[board]
+    }
+
+    pub fn is_all_solved(&self) -> bool {
+        self.solved.iter().all(|solved| *solved)
+    }
+
+    pub fn remained_words(&self, board: usize) -> &Vec<String> {
+        self.boards[board].remained_words()
+    }
+
+    pub fn remained_words_length(&self, board: usize) -> usize {
+        self.boards[board].remained_words_length()
+    }
+
+    pub fn reset(&mut self) {
+        for board in &mut self.boards {
+            board.reset();
+        }
+        self.solved = vec![false; self.boards.len()];
+        self.guess_words = self.all_guess_words.clone();
+        self.rank();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::score;
+
+    fn preset_words() -> Vec<String> {
+        vec![
+            "hello".to_string(),
+            "early".to_string(),
+            "asset".to_string(),
+            "bound".to_string(),
+            "heard".to_string(),
+        ]
+    }
+
+    #[test]
+    fn new() {
+        let target = MultiHelper::new(5, &preset_words(), 2);
+        assert_eq!(target.board_count(), 2);
+        assert_eq!(target.suggest().len(), 5);
+        assert_eq!(target.suggest()[0], "hello");
+        assert!((target.scores()[0] - 2.0 * 5f64.log2()).abs() < 1e-9);
+        assert_eq!(target.remained_words_length(1), 5);
+    }
+
+    #[test]
+    fn add_hints() {
+        let mut target = MultiHelper::new(5, &preset_words(), 2);
+        target.add_hints("hello", &[score("hello", "early"), score("hello", "asset")]);
+        assert_eq!(target.remained_words(0), &vec![String::from("early")]);
+        assert_eq!(target.remained_words(1), &vec![String::from("asset")]);
+        assert!(!target.is_solved(0));
+        assert!(!target.suggest().contains(&String::from("hello")));
+    }
+
+    #[test]
+    fn solve_boards() {
+        let mut target = MultiHelper::new(5, &preset_words(), 2);
+        target.add_hints("early", &[score("early", "early"), score("early", "asset")]);
+        assert!(target.is_solved(0));
+        assert!(!target.is_all_solved());
+        assert_eq!(target.suggest()[0], "asset");
+        target.add_hints("asset", &[Vec::new(), score("asset", "asset")]);
+        assert!(target.is_all_solved());
+    }
+
+    #[test]
+    fn reset() {
+        let mut target = MultiHelper::new(5, &preset_words(), 2);
+        let initial = target.suggest().clone();
+        target.add_hints("early", &[score("early", "early"), score("early", "asset")]);
+        target.reset();
+        assert!(!target.is_solved(0));
+        assert_eq!(target.suggest(), &initial);
+        assert_eq!(target.remained_words_length(0), 5);
+    }
+}
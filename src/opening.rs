@@ -0,0 +1,135 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Weights;
+use crate::txt::cache_dir;
+
+const OPENING_DIRNAME: &str = "openings";
+
+/// Ranked suggestions before the first guess, cached because ranking a large
+/// dictionary takes a while and always gives the same result.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Opening {
+    pub word_length: usize,
+    pub strategy: String,
+    /// `dictionary_hash` of the answers and guesses the words are ranked for.
+    pub dictionary_hash: String,
+    pub words: Vec<String>,
+    pub scores: Vec<f64>,
+}
+
+/// Hashes words with FNV-1a, which stays the same across builds unlike `DefaultHasher`.
+///
+/// Weights of the answers are hashed only if any are given, so the hash of
+/// equally likely answers stays the same as before weights are supported.
+pub fn dictionary_hash(answer_words: &[String], guess_words: &[String], weights: &Weights) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let mut feed = |bytes: &[u8]| {
+        for byte in bytes {
+            hash ^= *byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    };
+    for words in [answer_words, guess_words] {
+        for word in words {
+            feed(word.as_bytes());
+            feed(b"\n");
+        }
+        feed(b"\0");
+    }
+    if !weights.is_empty() {
+        for word in answer_words {
+            feed(&weights.get(word).copied().unwrap_or(1.0).to_bits().to_le_bytes());
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+impl Opening {
+    pub fn new(word_length: usize, strategy: &str, dictionary_hash: &str, words: &[String], scores: &[f64]) -> Self {
+        Opening {
+            word_length,
+            strategy: strategy.to_string(),
+            dictionary_hash: dictionary_hash.to_string(),
+            words: words.to_vec(),
+            scores: scores.to_vec(),
+        }
+    }
+
+    pub fn default_path(word_length: usize, strategy: &str, dictionary_hash: &str) -> PathBuf {
+        cache_dir().join(OPENING_DIRNAME).join(format!("{}-{}-{}.json", strategy, word_length, dictionary_hash))
+    }
+
+    /// Loads a cached opening, or `None` if nothing is cached at `path` for the same key.
+    pub fn load(path: &Path, word_length: usize, strategy: &str, dictionary_hash: &str) -> Result<Option<Self>, Box<dyn std::error::Error>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)?;
+        let opening: Opening = serde_json::from_str(&content)?;
+        if opening.word_length != word_length || opening.strategy != strategy || opening.dictionary_hash != dictionary_hash {
+            return Ok(None);
+        }
+        Ok(Some(opening))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn preset_words() -> Vec<String> {
+        vec!["hello".to_string(), "early".to_string()]
+    }
+
+    #[cfg(test)]
+    mod dictionary_hash {
+        use super::*;
+
+        #[test]
+        fn stable() {
+            assert_eq!(dictionary_hash(&preset_words(), &[], &Weights::new()), dictionary_hash(&preset_words(), &[], &Weights::new()));
+            assert_eq!(dictionary_hash(&[], &[], &Weights::new()).len(), 16);
+        }
+
+        #[test]
+        fn depends_on_words() {
+            let hash = dictionary_hash(&preset_words(), &[], &Weights::new());
+            assert_ne!(hash, dictionary_hash(&preset_words()[..1], &[], &Weights::new()));
+            assert_ne!(hash, dictionary_hash(&[], &preset_words(), &Weights::new()));
+            assert_ne!(hash, dictionary_hash(&["helloearly".to_string()], &[], &Weights::new()));
+        }
+
+        #[test]
+        fn depends_on_weights() {
+            let hash = dictionary_hash(&preset_words(), &[], &Weights::new());
+            let mut weights = Weights::new();
+            weights.insert(preset_words()[0].to_string(), 2.0);
+            assert_ne!(hash, dictionary_hash(&preset_words(), &[], &weights));
+        }
+    }
+
+    #[test]
+    fn save_and_load() {
+        let path = env::temp_dir().join("wordle-helper-test-opening").join("entropy-5-0.json");
+        let opening = Opening::new(5, "entropy", "0", &preset_words(), &[1.0, 0.5]);
+        opening.save(&path).unwrap();
+        assert_eq!(Opening::load(&path, 5, "entropy", "0").unwrap(), Some(opening));
+        assert_eq!(Opening::load(&path, 5, "minimax", "0").unwrap(), None);
+        assert_eq!(Opening::load(&path, 5, "entropy", "1").unwrap(), None);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(Opening::load(&path, 5, "entropy", "0").unwrap(), None);
+    }
+}
@@ -0,0 +1,222 @@
+use crate::{check_hard_mode, guess_pool, Hint, Helper};
+use crate::opening::Opening;
+use crate::simple::SimpleHelper;
+
+/// How a `RankedHelper` orders guesses over the remained answers.
+pub trait Ranking {
+    /// Scores `guess_words` over `answers`, ordered from the best guess.
+    fn rank(&self, guess_words: &[String], answers: &[String]) -> Vec<(String, f64)>;
+
+    /// Probability that `word` is the answer among `answers`, if the ranking weighs words.
+    fn probability(&self, _word: &str, _answers: &[String]) -> Option<f64> {
+        None
+    }
+}
+
+/// A helper which narrows answers with its filter, `SimpleHelper` unless
+/// hints may lie, and suggests guesses ordered by its `Ranking`, with scores
+/// of the ranking.
+#[derive(Debug)]
+pub struct RankedHelper<R, F = SimpleHelper> {
+    ranking: R,
+    filter: F,
+    all_guess_words: Vec<String>,
+    guess_words: Vec<String>,
+    hard_mode: bool,
+    history: Vec<Vec<Hint>>,
+    initial_ranked_words: Vec<String>,
+    initial_scores: Vec<f64>,
+    ranked_words: Vec<String>,
+    scores: Vec<f64>,
+}
+
+impl<R: Ranking> RankedHelper<R> {
+    /// Creates a helper which narrows `answer_words` and may also suggest
+    /// `guess_words`, ordered by `ranking`.
+    pub fn ranked(width: usize, answer_words: &[String], guess_words: &[String], ranking: R) -> Self {
+        Self::filtered(SimpleHelper::new(width, answer_words), width, answer_words, guess_words, ranking)
+    }
+
+    /// Creates a helper like `ranked`, but starts from `opening` ranked for
+    /// the same words before instead of ranking again.
+    pub fn opened(width: usize, answer_words: &[String], guess_words: &[String], ranking: R, opening: Opening) -> Self {
+        let mut helper = Self::unranked(SimpleHelper::new(width, answer_words), width, answer_words, guess_words, ranking);
+        helper.initial_ranked_words = opening.words.clone();
+        helper.initial_scores = opening.scores.clone();
+        helper.ranked_words = opening.words;
+        helper.scores = opening.scores;
+        helper
+    }
+}
+
+impl<R: Ranking, F: Helper> RankedHelper<R, F> {
+    /// Creates a helper like `ranked`, but narrows `answer_words` with `filter`.
+    pub fn filtered(filter: F, width: usize, answer_words: &[String], guess_words: &[String], ranking: R) -> Self {
+        let mut helper = Self::unranked(filter, width, answer_words, guess_words, ranking);
+        helper.rank();
+        helper.initial_ranked_words = helper.ranked_words.clone();
+        helper.initial_scores = helper.scores.clone();
+        helper
+    }
+
+    fn unranked(filter: F, width: usize, answer_words: &[String], guess_words: &[String], ranking: R) -> Self {
+        let all_guess_words = guess_pool(width, answer_words, guess_words);
+        RankedHelper {
+            ranking,
+            filter,
+            guess_words: all_guess_words.clone(),
+            all_guess_words,
+            hard_mode: false,
+            history: Vec::new(),
+            initial_ranked_words: Vec::new(),
+            initial_scores: Vec::new(),
+            ranked_words: Vec::new(),
+            scores: Vec::new(),
+        }
+    }
+
+    /// Restricts suggestions to guesses allowed in hard mode.
+    ///
+    /// Hard mode has nothing to restrict before the first hint, so this takes
+    /// effect from the next `add_hint` without ranking again.
+    pub fn with_hard_mode(mut self, hard_mode: bool) -> Self {
+        self.hard_mode = hard_mode;
+        self
+    }
+
+    fn apply_hint(&mut self, word: &str, hints: &[Hint]) {
+        self.filter.add_hint(word, hints);
+        self.guess_words.retain(|w| w != word);
+        self.history.push(hints.to_vec());
+    }
+
+    fn rank(&mut self) {
+        let allowed: Vec<String>;
+        let guess_words = match self.hard_mode && !self.history.is_empty() {
+            true => {
+                allowed = self.guess_words.iter()
+                    .filter(|word| {
+                        self.history.iter().all(|hints| check_hard_mode(word, hints).is_ok())
+                    })
+                    .cloned()
+                    .collect();
+                &allowed
+            }
+            false => &self.guess_words,
+        };
+        let (ranked_words, scores) = self.ranking.rank(guess_words, self.filter.remained_words()).into_iter().unzip();
+        self.ranked_words = ranked_words;
+        self.scores = scores;
+    }
+}
+
+impl<R: Ranking, F: Helper> Helper for RankedHelper<R, F> {
+    fn suggest(&self) -> &Vec<String> {
+        &self.ranked_words
+    }
+
+    fn add_hint(&mut self, word: &str, hints: &[Hint]) {
+        self.apply_hint(word, hints);
+        self.rank();
+    }
+
+    fn remained_words_length(&self) -> usize {
+        self.filter.remained_words_length()
+    }
+
+    fn remained_words(&self) -> &Vec<String> {
+        self.filter.remained_words()
+    }
+
+    fn scores(&self) -> Option<&Vec<f64>> {
+        Some(&self.scores)
+    }
+
+    fn probability(&self, word: &str) -> Option<f64> {
+        self.ranking.probability(word, self.filter.remained_words())
+    }
+
+    fn reset(&mut self) {
+        self.filter.reset();
+        self.guess_words = self.all_guess_words.clone();
+        self.history.clear();
+        self.ranked_words = self.initial_ranked_words.clone();
+        self.scores = self.initial_scores.clone();
+    }
+
+    fn rebuild(&mut self, rounds: &[(String, Vec<Hint>)]) {
+        self.reset();
+        if rounds.is_empty() {
+            return;
+        }
+        for (word, hints) in rounds {
+            self.apply_hint(word, hints);
+        }
+        self.rank();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::score;
+
+    /// Orders guesses alphabetically with their length as scores.
+    #[derive(Debug)]
+    struct Alphabetical;
+
+    impl Ranking for Alphabetical {
+        fn rank(&self, guess_words: &[String], _answers: &[String]) -> Vec<(String, f64)> {
+            let mut ranked: Vec<(String, f64)> = guess_words.iter()
+                .map(|word| {
+                    (word.to_string(), word.len() as f64)
+                })
+                .collect();
+            ranked.sort_by(|a, b| a.0.cmp(&b.0));
+            ranked
+        }
+    }
+
+    fn preset_words() -> Vec<String> {
+        vec![
+            "hello".to_string(),
+            "early".to_string(),
+            "asset".to_string(),
+        ]
+    }
+
+    #[test]
+    fn guess_pool_dedup() {
+        let actual = guess_pool(5, &preset_words(), &["early".to_string(), "heard".to_string(), "zo".to_string()]);
+        assert_eq!(actual, vec![
+            String::from("hello"),
+            String::from("early"),
+            String::from("asset"),
+            String::from("heard"),
+        ]);
+    }
+
+    #[test]
+    fn rank_and_rebuild() {
+        let mut target = RankedHelper::ranked(5, &preset_words(), &["heard".to_string()], Alphabetical);
+        assert_eq!(target.suggest(), &vec![
+            String::from("asset"),
+            String::from("early"),
+            String::from("heard"),
+            String::from("hello"),
+        ]);
+        target.add_hint("heard", &score("heard", "early"));
+        assert_eq!(target.remained_words(), &vec![String::from("early")]);
+        assert!(!target.suggest().contains(&String::from("heard")));
+        target.rebuild(&[]);
+        assert_eq!(target.suggest().len(), 4);
+        assert_eq!(target.probability("early"), None);
+    }
+
+    #[test]
+    fn hard_mode() {
+        let mut target = RankedHelper::ranked(5, &preset_words(), &[], Alphabetical).with_hard_mode(true);
+        target.add_hint("hello", &score("hello", "early"));
+        assert!(target.suggest().iter().all(|word| check_hard_mode(word, &score("hello", "early")).is_ok()));
+    }
+}
@@ -0,0 +1,102 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::txt::cache_dir;
+
+const SESSION_FILENAME: &str = "session.json";
+
+/// An interactive game in progress, saved after every round so it can be resumed.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub word_length: usize,
+    pub max_guess_count: usize,
+    pub dict_path: String,
+    pub guesses_path: Option<String>,
+    pub rounds: Vec<Round>,
+}
+
+/// A guessed word and its hint digits as they are input, like `01020`.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Round {
+    pub word: String,
+    pub pattern: String,
+}
+
+impl Session {
+    pub fn new(word_length: usize, max_guess_count: usize, dict_path: &str, guesses_path: Option<&str>) -> Self {
+        Session {
+            word_length,
+            max_guess_count,
+            dict_path: dict_path.to_string(),
+            guesses_path: guesses_path.map(|p| p.to_string()),
+            rounds: Vec::new(),
+        }
+    }
+
+    pub fn default_path() -> PathBuf {
+        cache_dir().join(SESSION_FILENAME)
+    }
+
+    pub fn add_round(&mut self, word: &str, pattern: &str) {
+        self.rounds.push(Round { word: word.to_string(), pattern: pattern.to_string() });
+    }
+
+    /// Loads a saved session, or `None` if nothing is saved at `path`.
+    pub fn load(path: &Path) -> Result<Option<Self>, Box<dyn std::error::Error>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn remove(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn preset_session() -> Session {
+        let mut session = Session::new(5, 6, "", Some("guesses.txt"));
+        session.add_round("bound", "00000");
+        session.add_round("spice", "10001");
+        session
+    }
+
+    #[test]
+    fn save_and_load() {
+        let path = env::temp_dir().join("wordle-helper-test").join("save_and_load.json");
+        let session = preset_session();
+        session.save(&path).unwrap();
+        assert_eq!(Session::load(&path).unwrap(), Some(session));
+        Session::remove(&path).unwrap();
+        assert_eq!(Session::load(&path).unwrap(), None);
+    }
+
+    #[test]
+    fn load_invalid() {
+        let path = env::temp_dir().join("wordle-helper-test").join("load_invalid.json");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "{").unwrap();
+        assert!(Session::load(&path).is_err());
+        Session::remove(&path).unwrap();
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{Hint, Spot};
+
+/// Emoji set used for share text.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Tiles {
+    Normal,
+    HighContrast,
+}
+
+impl Tiles {
+    fn tile(&self, spot: &Spot) -> char {
+        match (self, spot) {
+            (Tiles::Normal, Spot::At(_)) => '🟩',
+            (Tiles::Normal, Spot::InWithout(_)) => '🟨',
+            (Tiles::HighContrast, Spot::At(_)) => '🟧',
+            (Tiles::HighContrast, Spot::InWithout(_)) => '🟦',
+            (_, Spot::None()) => '⬛',
+        }
+    }
+}
+
+/// Converts one emoji tile of share text to a hint digit.
+///
+/// Both dark and light theme gray tiles and the high contrast orange/blue
+/// tiles are accepted.
+fn tile_to_digit(tile: char) -> Option<char> {
+    match tile {
+        '🟩' | '🟧' => Some('2'),
+        '🟨' | '🟦' => Some('1'),
+        '⬛' | '⬜' => Some('0'),
+        _ => None,
+    }
+}
+
+fn is_ignorable(c: char) -> bool {
+    c.is_whitespace() || c == '\u{fe0f}' || c == '\u{200d}'
+}
+
+/// Extracts hint digits of every row from share text like below.
+///
+/// ```text
+/// Wordle 1,234 3/6
+///
+/// ⬛🟨⬛⬛🟩
+/// 🟩⬛🟨⬛🟩
+/// 🟩🟩🟩🟩🟩
+/// ```
+///
+/// Lines without any tile, like the title, are skipped.
+pub fn parse_patterns(text: &str) -> Result<Vec<String>, String> {
+    let mut patterns: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let chars: Vec<char> = line.chars()
+            .filter(|c| {
+                !is_ignorable(*c)
+            })
+            .collect();
+        if !chars.iter().any(|c| tile_to_digit(*c).is_some()) {
+            continue;
+        }
+        let pattern: Option<String> = chars.iter()
+            .map(|c| {
+                tile_to_digit(*c)
+            })
+            .collect();
+        match pattern {
+            Some(p) => patterns.push(p),
+            None => return Err(format!("row {} has unknown tile: {}", patterns.len() + 1, line.trim())),
+        }
+    }
+    if patterns.is_empty() {
+        return Err(String::from("no tile rows are found"));
+    }
+    Ok(patterns)
+}
+
+/// Converts share text into hints of each round with the guessed `words` in order.
+pub fn parse_share(text: &str, words: &[String]) -> Result<Vec<Vec<Hint>>, String> {
+    let patterns = parse_patterns(text)?;
+    if patterns.len() != words.len() {
+        return Err(format!("share text has {} rows but {} words are given", patterns.len(), words.len()));
+    }
+    patterns.iter()
+        .zip(words)
+        .enumerate()
+        .map(|(i, (pattern, word))| {
+            Hint::from_pattern(word, pattern).map_err(|e| {
+                format!("row {} ({}): {}", i + 1, word, e)
+            })
+        })
+        .collect()
+}
+
+fn result(rounds_count: usize, solved: bool, max_guess_count: usize) -> String {
+    match solved {
+        true => format!("{}/{}", rounds_count, max_guess_count),
+        false => format!("X/{}", max_guess_count),
+    }
+}
+
+fn is_solved(rounds: &[Vec<Hint>]) -> bool {
+    matches!(rounds.last(), Some(hints) if Hint::all_at(hints))
+}
+
+/// Renders hints of each round as share text, which `parse_patterns` can read back.
+///
+/// The game is treated as solved when the last round is all `At`, otherwise
+/// the result is shown as `X/max_guess_count`.
+pub fn to_share_text(label: &str, rounds: &[Vec<Hint>], max_guess_count: usize, tiles: Tiles) -> String {
+    let mut lines: Vec<String> = vec![
+        format!("{} {}", label, result(rounds.len(), is_solved(rounds), max_guess_count)),
+        String::new(),
+    ];
+    for hints in rounds {
+        lines.push(hints.iter()
+            .map(|h| {
+                tiles.tile(&h.spot)
+            })
+            .collect());
+    }
+    lines.join("\n")
+}
+
+/// Renders guessed words and hint digits of each round as plain text.
+pub fn to_transcript(rounds: &[(String, Vec<Hint>)], max_guess_count: usize) -> String {
+    let mut lines: Vec<String> = rounds.iter()
+        .enumerate()
+        .map(|(i, (word, hints))| {
+            format!("{}. {} {}", i + 1, word.to_uppercase(), Hint::pattern(hints))
+        })
+        .collect();
+    let hints: Vec<Vec<Hint>> = rounds.iter()
+        .map(|(_, hints)| {
+            hints.clone()
+        })
+        .collect();
+    let solved = is_solved(&hints);
+    lines.push(format!("{} {}", if solved { "Solved" } else { "Failed" }, result(rounds.len(), solved, max_guess_count)));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::score;
+
+    #[cfg(test)]
+    mod parse_patterns {
+        use super::*;
+
+        #[test]
+        fn dark() {
+            let actual = parse_patterns("Wordle 1,234 3/6\n\n⬛🟨⬛⬛🟩\n🟩⬛🟨⬛🟩\n🟩🟩🟩🟩🟩");
+            assert_eq!(actual, Ok(vec![
+                String::from("01002"),
+                String::from("20102"),
+                String::from("22222"),
+            ]));
+        }
+
+        #[test]
+        fn light_with_variation_selector() {
+            let actual = parse_patterns("Wordle 1,234 X/6*\r\n\r\n⬜️🟨⬜️⬜️🟩\r\n");
+            assert_eq!(actual, Ok(vec![String::from("01002")]));
+        }
+
+        #[test]
+        fn high_contrast() {
+            let actual = parse_patterns("Wordle 1,234 2/6\n🟦⬛⬛🟧⬛\n🟧🟧🟧🟧🟧\n");
+            assert_eq!(actual, Ok(vec![String::from("10020"), String::from("22222")]));
+        }
+
+        #[test]
+        fn unknown_tile() {
+            assert!(parse_patterns("⬛🟨⬛⬛🟩\n🟩⬛x⬛🟩").is_err());
+        }
+
+        #[test]
+        fn no_rows() {
+            assert!(parse_patterns("Wordle 1,234 3/6").is_err());
+        }
+    }
+
+    #[cfg(test)]
+    mod to_share_text {
+        use super::*;
+
+        #[test]
+        fn solved() {
+            let actual = to_share_text("Wordle 1,234", &[score("solve", "lover"), score("lover", "lover")], 6, Tiles::Normal);
+            assert_eq!(actual, "Wordle 1,234 2/6\n\n⬛🟩🟨🟨🟨\n🟩🟩🟩🟩🟩");
+            assert_eq!(parse_patterns(&actual), Ok(vec![String::from("02111"), String::from("22222")]));
+        }
+
+        #[test]
+        fn failed_high_contrast() {
+            let actual = to_share_text("Wordle", &[score("solve", "lover"), score("hello", "lover")], 2, Tiles::HighContrast);
+            assert_eq!(actual, "Wordle X/2\n\n⬛🟧🟦🟦🟦\n⬛🟦🟦⬛🟦");
+        }
+    }
+
+    #[cfg(test)]
+    mod to_transcript {
+        use super::*;
+
+        #[test]
+        fn solved() {
+            let actual = to_transcript(&[
+                ("solve".to_string(), score("solve", "lover")),
+                ("lover".to_string(), score("lover", "lover")),
+            ], 6);
+            assert_eq!(actual, "1. SOLVE 02111\n2. LOVER 22222\nSolved 2/6");
+        }
+
+        #[test]
+        fn failed() {
+            let actual = to_transcript(&[("solve".to_string(), score("solve", "lover"))], 1);
+            assert_eq!(actual, "1. SOLVE 02111\nFailed X/1");
+        }
+    }
+
+    #[cfg(test)]
+    mod parse_share {
+        use super::*;
+
+        #[test]
+        fn valid() {
+            let actual = parse_share(
+                "Wordle 1,234 2/6\n\n⬛🟩🟨🟨🟨\n🟩🟩🟩🟩🟩",
+                &["solve".to_string(), "lover".to_string()],
+            );
+            assert_eq!(actual, Ok(vec![score("solve", "lover"), score("lover", "lover")]));
+        }
+
+        #[test]
+        fn japanese() {
+            let actual = parse_share("🟩⬛🟩⬛🟨", &["バタフリー".to_string()]);
+            assert_eq!(actual, Ok(vec![score("バタフリー", "バクフーン")]));
+        }
+
+        #[test]
+        fn words_count_mismatch() {
+            assert!(parse_share("⬛🟩🟨🟨🟨\n🟩🟩🟩🟩🟩", &["solve".to_string()]).is_err());
+        }
+
+        #[test]
+        fn word_length_mismatch() {
+            assert!(parse_share("⬛🟩🟨🟨🟨", &["solves".to_string()]).is_err());
+        }
+    }
+}
@@ -1,65 +1,92 @@
+use std::collections::HashMap;
+
 use crate::{Hint, Helper, Spot};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct LetterCount {
+    pub(crate) min: usize,
+    pub(crate) max: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
 pub struct SimpleHelper {
     width: usize,
+    all_words: Vec<String>,
     dict_words: Vec<String>,
 }
 
 impl SimpleHelper {
     pub fn new(width: usize, dict_words: &[String]) -> SimpleHelper {
+        let all_words: Vec<String> = dict_words.iter()
+            .filter(|word| {
+                word.chars().count() == width
+            })
+            .map(|word| {
+                word.to_string()
+            }).collect();
         SimpleHelper {
             width,
-            dict_words: dict_words.iter()
-                .filter(|word| {
-                    word.chars().count() == width
-                })
-                .map(|word| {
-                    word.to_string()
-                }).collect(),
+            dict_words: all_words.clone(),
+            all_words,
         }
     }
 
-    fn shrink_hints(hints: &[Hint]) -> Vec<Hint> {
-        let mut results: Vec<Hint> = Vec::new();
+    /// Derives how many times each letter of the guess appears in the answer.
+    ///
+    /// `At` and `InWithout` hints raise the minimum count, and a `None()` hint
+    /// caps the count at that minimum, so a gray duplicate of a yellow or green
+    /// letter means the answer has exactly that many.
+    pub(crate) fn count_letters(hints: &[Hint]) -> HashMap<char, LetterCount> {
+        let mut counts: HashMap<char, LetterCount> = HashMap::new();
         for hint in hints {
-            if hint.spot == Spot::None()
-                && hints
-                .iter()
-                .filter(|h| {
-                    h.letter == hint.letter
-                        && (matches!(h.spot, Spot::InWithout(_)) || matches!(h.spot, Spot::At(_)))
-                }).count() > 0 {
-                continue;
+            let count = counts.entry(hint.letter).or_insert(LetterCount { min: 0, max: None });
+            match hint.spot {
+                Spot::At(_) | Spot::InWithout(_) => count.min += 1,
+                Spot::None() => count.max = Some(0),
+            }
+        }
+        for count in counts.values_mut() {
+            if count.max.is_some() {
+                count.max = Some(count.min);
             }
-            results.push(hint.clone());
         }
-        results
+        counts
+    }
+
+    /// Returns whether `word` could be the answer for one round of hints.
+    ///
+    /// Hints are expected in guess order, so a `None()` hint also means the
+    /// letter is not at the position of that hint.
+    pub fn matches(word: &str, hints: &[Hint]) -> bool {
+        let chars: Vec<char> = word.chars().collect();
+        for (i, hint) in hints.iter().enumerate() {
+            let res = match hint.spot {
+                Spot::None() => chars.get(i) != Some(&hint.letter),
+                Spot::InWithout(spot) => chars.get(spot) != Some(&hint.letter),
+                Spot::At(at_spot) => chars.get(at_spot) == Some(&hint.letter),
+            };
+            if !res {
+                return false;
+            }
+        }
+        for (letter, count) in Self::count_letters(hints) {
+            let actual = chars.iter().filter(|c| **c == letter).count();
+            if actual < count.min {
+                return false;
+            }
+            if let Some(max) = count.max {
+                if actual > max {
+                    return false;
+                }
+            }
+        }
+        true
     }
 
     fn update_with_hints(&mut self, hints: &[Hint]) {
         self.dict_words = self.dict_words.iter()
             .filter(|word| {
-                for hint in Self::shrink_hints(hints) {
-                    let res = match &hint.spot {
-                        Spot::None() => {
-                            !word.contains(hint.letter)
-                        }
-                        Spot::InWithout(spot) => {
-                            if !word.contains(hint.letter) {
-                                return false;
-                            }
-                            word.chars().nth(*spot as usize).unwrap() != hint.letter
-                        }
-                        Spot::At(at_spot) => {
-                            word.chars().nth(*at_spot as usize).unwrap() == hint.letter
-                        }
-                    };
-                    if !res {
-                        return false;
-                    }
-                }
-                true
+                Self::matches(word, hints)
             }).cloned()
             .collect();
     }
@@ -87,11 +114,20 @@ impl Helper for SimpleHelper {
     fn remained_words_length(&self) -> usize {
         self.dict_words.len()
     }
+
+    fn remained_words(&self) -> &Vec<String> {
+        &self.dict_words
+    }
+
+    fn reset(&mut self) {
+        self.dict_words = self.all_words.clone();
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::score;
 
     #[cfg(test)]
     mod new {
@@ -117,41 +153,61 @@ mod tests {
     }
 
     #[cfg(test)]
-    mod shrink_words {
+    mod count_letters {
         use super::*;
 
         #[test]
-        fn remove_none_when_has_at() {
-            let actual = SimpleHelper::shrink_hints(&[
+        fn exact_when_none_with_at() {
+            let actual = SimpleHelper::count_letters(&[
                 Hint { letter: 'r', spot: Spot::None() },
                 Hint { letter: 'o', spot: Spot::At(1) },
                 Hint { letter: 'b', spot: Spot::None() },
                 Hint { letter: 'o', spot: Spot::None() },
                 Hint { letter: 't', spot: Spot::At(4) }
             ]);
-            assert_eq!(actual, vec![
-                Hint { letter: 'r', spot: Spot::None() },
-                Hint { letter: 'o', spot: Spot::At(1) },
-                Hint { letter: 'b', spot: Spot::None() },
-                Hint { letter: 't', spot: Spot::At(4) },
-            ])
+            assert_eq!(actual[&'r'], LetterCount { min: 0, max: Some(0) });
+            assert_eq!(actual[&'o'], LetterCount { min: 1, max: Some(1) });
+            assert_eq!(actual[&'b'], LetterCount { min: 0, max: Some(0) });
+            assert_eq!(actual[&'t'], LetterCount { min: 1, max: None });
         }
 
         #[test]
-        fn remove_none_when_has_in_without() {
-            let actual = SimpleHelper::shrink_hints(&[
+        fn exact_when_none_with_in_without() {
+            let actual = SimpleHelper::count_letters(&[
                 Hint { letter: 't', spot: Spot::None() },
                 Hint { letter: 'a', spot: Spot::InWithout(1) },
                 Hint { letter: 'y', spot: Spot::InWithout(2) },
                 Hint { letter: 'r', spot: Spot::None() },
                 Hint { letter: 'a', spot: Spot::None() }
             ]);
-            assert_eq!(actual, vec![
-                Hint { letter: 't', spot: Spot::None() },
-                Hint { letter: 'a', spot: Spot::InWithout(1) },
-                Hint { letter: 'y', spot: Spot::InWithout(2) },
-                Hint { letter: 'r', spot: Spot::None() },
-            ]);
+            assert_eq!(actual[&'a'], LetterCount { min: 1, max: Some(1) });
+            assert_eq!(actual[&'y'], LetterCount { min: 1, max: None });
+        }
+
+        #[test]
+        fn llama() {
+            let actual = SimpleHelper::count_letters(&score("llama", "lilac"));
+            assert_eq!(actual[&'l'], LetterCount { min: 2, max: None });
+            assert_eq!(actual[&'a'], LetterCount { min: 1, max: Some(1) });
+            assert_eq!(actual[&'m'], LetterCount { min: 0, max: Some(0) });
+        }
+    }
+
+    #[cfg(test)]
+    mod matches {
+        use super::*;
+
+        #[test]
+        fn hints_in_guess_order() {
+            assert!(SimpleHelper::matches("early", &score("eerie", "early")));
+        }
+
+        #[test]
+        fn hints_out_of_order() {
+            let mut hints = score("eerie", "early");
+            hints.swap(0, 1);
+            // The gray E now says E is not the 1st letter.
+            assert!(!SimpleHelper::matches("early", &hints));
         }
     }
 
@@ -306,5 +362,70 @@ mod tests {
                 ]);
             }
         }
+
+        #[cfg(test)]
+        mod rebuild {
+            use super::*;
+
+            #[test]
+            fn reset() {
+                let mut target = SimpleHelper::new(5, &preset_words());
+                target.add_hint("dummy", &[Hint { letter: 't', spot: Spot::At(4) }]);
+                target.reset();
+                assert_eq!(target.suggest(), &preset_words());
+            }
+
+            #[test]
+            fn corrected_rounds() {
+                let mut target = SimpleHelper::new(5, &preset_words());
+                target.add_hint("hello", &score("hello", "asset"));
+                assert_eq!(target.suggest(), &vec![String::from("asset")]);
+                target.rebuild(&[("hello".to_string(), score("hello", "early"))]);
+                assert_eq!(target.suggest(), &vec![String::from("early")]);
+                target.rebuild(&[("early".to_string(), score("early", "asset"))]);
+                assert_eq!(target.suggest(), &vec![String::from("asset")]);
+            }
+        }
+
+        #[cfg(test)]
+        mod duplicate {
+            use super::*;
+
+            #[test]
+            fn skill() {
+                let mut target = SimpleHelper::new(5, &[
+                    "early".to_string(),
+                    "hello".to_string(),
+                    "belly".to_string(),
+                    "apple".to_string(),
+                    "smell".to_string()]);
+                target.add_hint("skill", &score("skill", "early"));
+                assert_eq!(target.suggest(), &vec![String::from("early"), String::from("apple")]);
+            }
+
+            #[test]
+            fn geese() {
+                let mut target = SimpleHelper::new(5, &[
+                    "those".to_string(),
+                    "tease".to_string(),
+                    "sense".to_string(),
+                    "loose".to_string(),
+                    "geese".to_string()]);
+                target.add_hint("geese", &score("geese", "those"));
+                assert_eq!(target.suggest(), &vec![String::from("those"), String::from("loose")]);
+            }
+
+            #[test]
+            fn llama() {
+                let mut target = SimpleHelper::new(5, &[
+                    "lilac".to_string(),
+                    "llama".to_string(),
+                    "lapel".to_string(),
+                    "allay".to_string(),
+                    "lanai".to_string()]);
+                target.add_hint("llama", &score("llama", "lilac"));
+                assert_eq!(target.suggest(), &vec![String::from("lilac"), String::from("lapel")]);
+            }
+        }
     }
 }
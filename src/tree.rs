@@ -0,0 +1,387 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Hint, Helper, score};
+use crate::simple::SimpleHelper;
+
+/// A guess of a decision tree and the next guesses by its feedback pattern.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Node {
+    pub guess: String,
+    /// Number of answers remained before the guess.
+    pub remained: usize,
+    /// Next guesses by hint digits like `01020`, without the pattern solving the game.
+    pub children: BTreeMap<String, Node>,
+}
+
+impl Node {
+    /// Whether the guess is one of the remained answers.
+    pub fn solves(&self) -> bool {
+        self.remained > self.children.values().map(|child| child.remained).sum()
+    }
+
+    /// Answers solved under the node.
+    pub fn answers(&self) -> Vec<&str> {
+        let mut answers: Vec<&str> = Vec::new();
+        if self.solves() {
+            answers.push(&self.guess);
+        }
+        for child in self.children.values() {
+            answers.extend(child.answers());
+        }
+        answers
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub answers: usize,
+    pub max_depth: usize,
+    /// Average number of guesses to solve an answer.
+    pub average_guesses: f64,
+    /// Number of answers by number of guesses.
+    pub histogram: BTreeMap<usize, usize>,
+}
+
+impl Stats {
+    pub fn of(root: &Node) -> Self {
+        let mut histogram: BTreeMap<usize, usize> = BTreeMap::new();
+        Self::count(root, 1, &mut histogram);
+        let answers: usize = histogram.values().sum();
+        let total_guesses: usize = histogram.iter().map(|(depth, count)| depth * count).sum();
+        Stats {
+            answers,
+            max_depth: histogram.keys().last().copied().unwrap_or(0),
+            average_guesses: if answers == 0 { 0.0 } else { total_guesses as f64 / answers as f64 },
+            histogram,
+        }
+    }
+
+    fn count(node: &Node, depth: usize, histogram: &mut BTreeMap<usize, usize>) {
+        if node.solves() {
+            *histogram.entry(depth).or_insert(0) += 1;
+        }
+        for child in node.children.values() {
+            Self::count(child, depth + 1, histogram);
+        }
+    }
+}
+
+/// A decision tree as it is exported to JSON.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tree {
+    pub version: u32,
+    pub word_length: usize,
+    pub strategy: String,
+    pub stats: Stats,
+    pub root: Node,
+}
+
+impl Tree {
+    pub fn new(word_length: usize, strategy: &str, root: Node) -> Self {
+        Tree {
+            version: 1,
+            word_length,
+            strategy: strategy.to_string(),
+            stats: Stats::of(&root),
+            root,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Tells why the tree cannot be walked for `answer_words` of `word_length`, if any.
+    pub fn check(&self, word_length: usize, answer_words: &[String]) -> Result<(), String> {
+        if self.word_length != word_length {
+            return Err(format!("decision tree is built for words of length {}", self.word_length));
+        }
+        let expected: HashSet<&str> = answer_words.iter()
+            .filter(|word| {
+                word.chars().count() == word_length
+            })
+            .map(|word| {
+                word.as_str()
+            })
+            .collect();
+        let actual: HashSet<&str> = self.root.answers().into_iter().collect();
+        if expected != actual {
+            return Err(format!("decision tree is built for other answers ({} missing from the tree, {} not in the dictionary)",
+                               expected.difference(&actual).count(), actual.difference(&expected).count()));
+        }
+        Ok(())
+    }
+
+    /// Formats the stats and the tree indented by depth, like `  01020 sloth 12`.
+    pub fn text(&self) -> String {
+        let histogram: Vec<String> = self.stats.histogram.iter()
+            .map(|(depth, count)| {
+                format!("{}: {}", depth, count)
+            })
+            .collect();
+        let mut lines = vec![
+            format!("answers: {}", self.stats.answers),
+            format!("max depth: {}", self.stats.max_depth),
+            format!("average guesses: {:.4}", self.stats.average_guesses),
+            format!("histogram: {}", histogram.join(", ")),
+            String::new(),
+            format!("{} {}", self.root.guess, self.root.remained),
+        ];
+        Self::push_children(&self.root, 1, &mut lines);
+        lines.join("\n")
+    }
+
+    fn push_children(node: &Node, depth: usize, lines: &mut Vec<String>) {
+        for (pattern, child) in &node.children {
+            lines.push(format!("{}{} {} {}", "  ".repeat(depth), pattern, child.guess, child.remained));
+            Self::push_children(child, depth + 1, lines);
+        }
+    }
+}
+
+/// Builds a decision tree by always guessing the first suggestion of `helper`
+/// for every feedback pattern, or `None` if no answers are remained.
+pub fn build(helper: &mut dyn Helper) -> Option<Node> {
+    let node = grow(helper, &mut Vec::new(), usize::MAX);
+    helper.reset();
+    node
+}
+
+fn group(guess: &str, answers: &[String]) -> BTreeMap<String, Vec<Hint>> {
+    answers.iter()
+        .map(|answer| {
+            let hints = score(guess, answer);
+            (Hint::pattern(&hints), hints)
+        })
+        .collect()
+}
+
+/// Grows a node for the rounds, or `None` unless fewer answers than `limit` are remained.
+///
+/// Helpers tolerating lies keep answers in more than one group, so groups which
+/// do not shrink are left out to make sure the tree ends.
+fn grow(helper: &mut dyn Helper, rounds: &mut Vec<(String, Vec<Hint>)>, limit: usize) -> Option<Node> {
+    helper.rebuild(rounds);
+    let answers = helper.remained_words().clone();
+    if answers.len() >= limit {
+        return None;
+    }
+    let first = answers.first()?;
+    let mut guess = helper.suggest().first().unwrap_or(first).to_string();
+    let mut groups = group(&guess, &answers);
+    if groups.len() == 1 && !answers.contains(&guess) {
+        // The suggestion tells nothing, so guess an answer to make progress.
+        guess = first.to_string();
+        groups = group(&guess, &answers);
+    }
+
+    let mut children: BTreeMap<String, Node> = BTreeMap::new();
+    for (pattern, hints) in groups {
+        if Hint::all_at(&hints) {
+            continue;
+        }
+        rounds.push((guess.to_string(), hints));
+        if let Some(child) = grow(helper, rounds, answers.len()) {
+            children.insert(pattern, child);
+        }
+        rounds.pop();
+    }
+    Some(Node { guess, remained: answers.len(), children })
+}
+
+/// A helper which suggests guesses by walking a decision tree.
+///
+/// Once a round leaves the tree, it suggests remained words like `SimpleHelper`.
+#[derive(Debug)]
+pub struct TreeHelper {
+    filter: SimpleHelper,
+    root: Node,
+    path: Option<Vec<String>>,
+    suggested: Vec<String>,
+}
+
+impl TreeHelper {
+    pub fn new(width: usize, dict_words: &[String], root: Node) -> TreeHelper {
+        let mut helper = TreeHelper {
+            filter: SimpleHelper::new(width, dict_words),
+            root,
+            path: Some(Vec::new()),
+            suggested: Vec::new(),
+        };
+        helper.update_suggested();
+        helper
+    }
+
+    fn current(&self) -> Option<&Node> {
+        let mut node = &self.root;
+        for pattern in self.path.as_ref()? {
+            node = node.children.get(pattern)?;
+        }
+        Some(node)
+    }
+
+    fn update_suggested(&mut self) {
+        self.suggested = match self.current() {
+            Some(node) => vec![node.guess.to_string()],
+            None => self.filter.suggest().clone(),
+        };
+    }
+}
+
+impl Helper for TreeHelper {
+    fn suggest(&self) -> &Vec<String> {
+        &self.suggested
+    }
+
+    fn add_hint(&mut self, word: &str, hints: &[Hint]) {
+        self.filter.add_hint(word, hints);
+        let on_tree = matches!(self.current(), Some(node) if node.guess == word && node.children.contains_key(&Hint::pattern(hints)));
+        match (on_tree, &mut self.path) {
+            (true, Some(path)) => path.push(Hint::pattern(hints)),
+            _ => self.path = None,
+        }
+        self.update_suggested();
+    }
+
+    fn remained_words_length(&self) -> usize {
+        self.filter.remained_words_length()
+    }
+
+    fn remained_words(&self) -> &Vec<String> {
+        self.filter.remained_words()
+    }
+
+    fn reset(&mut self) {
+        self.filter.reset();
+        self.path = Some(Vec::new());
+        self.update_suggested();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fibble::FibbleHelper;
+
+    fn preset_words() -> Vec<String> {
+        vec![
+            "hello".to_string(),
+            "early".to_string(),
+            "asset".to_string(),
+            "bound".to_string(),
+            "beard".to_string(),
+            "bears".to_string(),
+        ]
+    }
+
+    fn preset_tree() -> Node {
+        build(&mut SimpleHelper::new(5, &preset_words())).unwrap()
+    }
+
+    #[cfg(test)]
+    mod build {
+        use super::*;
+
+        #[test]
+        fn covers_all_answers() {
+            let root = preset_tree();
+            assert_eq!(root.guess, "hello");
+            assert_eq!(root.remained, 6);
+            assert!(root.solves());
+            let stats = Stats::of(&root);
+            assert_eq!(stats.answers, 6);
+            assert_eq!(stats.histogram.values().sum::<usize>(), 6);
+        }
+
+        #[test]
+        fn splits_same_pattern() {
+            let root = preset_tree();
+            let child = &root.children[&Hint::pattern(&score("hello", "bears"))];
+            assert_eq!(child.remained, 2);
+            assert_eq!(child.guess, "bears");
+            assert_eq!(child.children.len(), 1);
+            assert_eq!(Stats::of(&root).max_depth, 3);
+        }
+
+        #[test]
+        fn empty() {
+            assert_eq!(build(&mut SimpleHelper::new(5, &[])), None);
+        }
+
+        #[test]
+        fn ends_with_lies() {
+            let mut words = preset_words();
+            words.push("heard".to_string());
+            let root = build(&mut FibbleHelper::new(5, &words, 1)).unwrap();
+            assert_eq!(root.remained, 7);
+        }
+    }
+
+    #[test]
+    fn check() {
+        let tree = Tree::new(5, "simple", preset_tree());
+        assert_eq!(tree.check(5, &preset_words()), Ok(()));
+        assert_eq!(tree.check(6, &preset_words()), Err(String::from("decision tree is built for words of length 5")));
+        assert_eq!(tree.check(5, &preset_words()[1..]),
+                   Err(String::from("decision tree is built for other answers (0 missing from the tree, 1 not in the dictionary)")));
+    }
+
+    #[test]
+    fn text() {
+        let root = Node {
+            guess: "hello".to_string(),
+            remained: 3,
+            children: BTreeMap::from([
+                ("01000".to_string(), Node { guess: "early".to_string(), remained: 2, children: BTreeMap::from([
+                    ("10010".to_string(), Node { guess: "asset".to_string(), remained: 1, children: BTreeMap::new() }),
+                ]) }),
+            ]),
+        };
+        assert_eq!(Tree::new(5, "simple", root).text(), "\
+answers: 3
+max depth: 3
+average guesses: 2.0000
+histogram: 1: 1, 2: 1, 3: 1
+
+hello 3
+  01000 early 2
+    10010 asset 1");
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let tree = Tree::new(5, "simple", preset_tree());
+        let json = serde_json::to_string(&tree).unwrap();
+        assert_eq!(serde_json::from_str::<Tree>(&json).unwrap(), tree);
+    }
+
+    #[cfg(test)]
+    mod tree_helper {
+        use super::*;
+
+        #[test]
+        fn walk() {
+            let mut target = TreeHelper::new(5, &preset_words(), preset_tree());
+            assert_eq!(target.suggest(), &vec![String::from("hello")]);
+            target.add_hint("hello", &score("hello", "beard"));
+            assert_eq!(target.suggest(), &vec![String::from("bears")]);
+            target.add_hint("bears", &score("bears", "beard"));
+            assert_eq!(target.suggest(), &vec![String::from("beard")]);
+            assert_eq!(target.remained_words_length(), 1);
+        }
+
+        #[test]
+        fn leave_tree() {
+            let mut target = TreeHelper::new(5, &preset_words(), preset_tree());
+            target.add_hint("bound", &score("bound", "early"));
+            assert_eq!(target.suggest(), target.remained_words());
+            target.reset();
+            assert_eq!(target.suggest(), &vec![String::from("hello")]);
+        }
+    }
+}
@@ -1,115 +1,297 @@
+use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::fs::File;
 use std::io;
-use std::io::{BufRead, copy};
-use std::path::Path;
+use std::io::{BufRead, Read, Write};
+use std::path::{Path, PathBuf};
 
-use ansi_term::Color::Yellow;
-use ansi_term::Style;
-use dialoguer::Confirm;
-use dialoguer::theme::ColorfulTheme;
-
-use crate::Dictionary;
+use crate::{Dictionary, Weights};
+use crate::error::Error;
 
 const DEFAULT_CACHE_DIR: &str = "wordle-helper";
 const DEFAULT_FILENAME: &str = "words_alpha.txt";
 
-fn default_dict_path() -> Box<Path> {
-    let buf = match env::var("XDG_CACHE_HOME") {
-        Ok(v) => Path::new(v.as_str()).join(DEFAULT_CACHE_DIR).join(DEFAULT_FILENAME),
+pub(crate) fn cache_dir() -> PathBuf {
+    match env::var("XDG_CACHE_HOME") {
+        Ok(v) => Path::new(v.as_str()).join(DEFAULT_CACHE_DIR),
         Err(_) => match env::var("HOME") {
             Ok(v) => {
-                Path::new(v.as_str()).join(".cache").join(DEFAULT_CACHE_DIR).join(DEFAULT_FILENAME)
+                Path::new(v.as_str()).join(".cache").join(DEFAULT_CACHE_DIR)
             }
             Err(_) => {
-                Path::new("/tmp").join(DEFAULT_CACHE_DIR).join(DEFAULT_FILENAME)
+                Path::new("/tmp").join(DEFAULT_CACHE_DIR)
             }
         }
-    };
-    buf.into_boxed_path()
+    }
+}
+
+fn default_dict_path() -> Box<Path> {
+    cache_dir().join(DEFAULT_FILENAME).into_boxed_path()
 }
 
 const ENGLISH_WORDS_URL: &str = "https://raw.githubusercontent.com/dwyl/english-words/master/words_alpha.txt";
 
-fn fetch_from_english_words(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
-    println!("{}",
-             Style::new().fg(Yellow).paint(
-                 format!("Downloading {}", ENGLISH_WORDS_URL)
-             )
-    );
+/// What to do when the default dictionary is not cached yet.
+pub enum DownloadPolicy {
+    /// Fails with `Error::NotFound`.
+    Never,
+    /// Downloads only if the callback agrees with the path to save it,
+    /// or fails with `Error::DownloadDeclined`.
+    Ask(Box<dyn FnOnce(&Path) -> bool>),
+    Always,
+}
+
+/// Progress of downloading the default dictionary.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Progress {
+    Started { url: String, total: Option<u64> },
+    Received { bytes: u64, total: Option<u64> },
+    Finished,
+}
+
+fn fetch_from_english_words(path: &Path, progress: &mut dyn FnMut(Progress)) -> Result<(), Error> {
     fs::create_dir_all(path.parent().unwrap())?;
-    let resp = reqwest::blocking::get(ENGLISH_WORDS_URL)?;
-    let mut dest = File::create(path)?;
-    let content = resp.text()?;
-    copy(&mut content.as_bytes(), &mut dest)?;
-    println!("{}",
-             Style::new().fg(Yellow).paint(
-                 "Complete!"
-             )
-    );
+    let mut resp = reqwest::blocking::get(ENGLISH_WORDS_URL)
+        .and_then(|resp| resp.error_for_status())
+        .map_err(|e| Error::Download(e.to_string()))?;
+    let total = resp.content_length();
+    progress(Progress::Started { url: ENGLISH_WORDS_URL.to_string(), total });
+    // Written aside first, so an interrupted download is not taken for the dictionary.
+    let partial = path.with_extension("part");
+    let mut dest = File::create(&partial)?;
+    let mut buf = [0u8; 64 * 1024];
+    let mut bytes = 0;
+    loop {
+        let n = resp.read(&mut buf).map_err(|e| Error::Download(e.to_string()))?;
+        if n == 0 {
+            break;
+        }
+        dest.write_all(&buf[..n])?;
+        bytes += n as u64;
+        progress(Progress::Received { bytes, total });
+    }
+    fs::rename(&partial, path)?;
+    progress(Progress::Finished);
     Ok(())
 }
 
-pub struct TxtDictionary {
-    file: File,
+/// Loads a `TxtDictionary` without prompting or printing, with the policy
+/// to download the default dictionary.
+pub struct TxtLoader {
+    path: Option<PathBuf>,
+    guesses: Option<PathBuf>,
+    download: DownloadPolicy,
+    progress: Box<dyn FnMut(Progress)>,
 }
 
-impl TxtDictionary {
-    pub fn new(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        if path.is_empty() {
-            let default_path = default_dict_path();
-            if !default_path.exists() {
-                println!();
-                println!("{}",
-                         Style::new().fg(Yellow).paint(
-                             format!("Default dictionary is not found at {}", default_path.to_str().unwrap())
-                         )
-                );
-                println!("{}",
-                         Style::new().fg(Yellow).paint(
-                             "So I should download dictionary from https://github.com/dwyl/english-words (about 4.04MB)"
-                         )
-                );
-                if Confirm::with_theme(&ColorfulTheme::default())
-                    .with_prompt("Do you want to continue?")
-                    .interact()
-                    .unwrap()
-                {
-                    fetch_from_english_words(&default_path)?
+impl TxtLoader {
+    /// Creates a loader of the default dictionary which never downloads it.
+    pub fn new() -> TxtLoader {
+        TxtLoader {
+            path: None,
+            guesses: None,
+            download: DownloadPolicy::Never,
+            progress: Box::new(|_| {}),
+        }
+    }
+
+    /// Loads answers from `path` instead of the default dictionary.
+    pub fn with_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Also loads words allowed to guess from `path`.
+    pub fn with_guesses(mut self, path: impl AsRef<Path>) -> Self {
+        self.guesses = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn with_download(mut self, download: DownloadPolicy) -> Self {
+        self.download = download;
+        self
+    }
+
+    /// Reports the progress of downloading the default dictionary to `progress`.
+    pub fn with_progress(mut self, progress: impl FnMut(Progress) + 'static) -> Self {
+        self.progress = Box::new(progress);
+        self
+    }
+
+    pub fn load(mut self) -> Result<TxtDictionary, Error> {
+        let mut dictionary = match self.path.take() {
+            Some(path) => TxtDictionary::open(&path)?,
+            None => self.load_default()?,
+        };
+        if let Some(path) = &self.guesses {
+            dictionary.guesses = TxtDictionary::index(path)?.0;
+        }
+        Ok(dictionary)
+    }
+
+    /// Loads the default dictionary, or the words compiled into the binary if
+    /// it is not cached, or downloads it first if the policy allows.
+    fn load_default(&mut self) -> Result<TxtDictionary, Error> {
+        let path = default_dict_path().to_path_buf();
+        if !path.exists() {
+            if let Some(dictionary) = common_words() {
+                return dictionary;
+            }
+            match std::mem::replace(&mut self.download, DownloadPolicy::Never) {
+                DownloadPolicy::Never => return Err(Error::NotFound(path)),
+                DownloadPolicy::Ask(confirm) => {
+                    if !confirm(&path) {
+                        return Err(Error::DownloadDeclined);
+                    }
                 }
+                DownloadPolicy::Always => {}
             }
-            let file = File::open(default_path)?;
-            Ok(TxtDictionary { file })
-        } else {
-            let file = File::open(path)?;
-            Ok(TxtDictionary { file })
+            fetch_from_english_words(&path, self.progress.as_mut())?;
         }
+        TxtDictionary::open(&path)
+    }
+}
+
+/// Common 5-letter words as answers and rarer ones as guesses, compiled into
+/// the binary with the `common-words` feature.
+///
+/// They are gathered from word lists of BIP39, names and petname, not from Wordle.
+#[cfg(feature = "common-words")]
+fn common_words() -> Option<Result<TxtDictionary, Error>> {
+    const ANSWERS: &[u8] = include_bytes!("../assets/answers.txt.gz");
+    const GUESSES: &[u8] = include_bytes!("../assets/guesses.txt.gz");
+    let read = |name: &str, bytes: &[u8]| {
+        TxtDictionary::read_index(io::BufReader::new(flate2::read::GzDecoder::new(bytes)), Path::new(name))
+    };
+    Some(read("assets/answers.txt.gz", ANSWERS).and_then(|(words, weights)| {
+        Ok(TxtDictionary { words, guesses: read("assets/guesses.txt.gz", GUESSES)?.0, weights })
+    }))
+}
+
+#[cfg(not(feature = "common-words"))]
+fn common_words() -> Option<Result<TxtDictionary, Error>> {
+    None
+}
+
+impl Default for TxtLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Words grouped by the number of chars.
+type WordIndex = HashMap<usize, Vec<String>>;
+
+/// A dictionary of a word per line, loaded into memory once so words of any
+/// length can be extracted again and again.
+///
+/// A word may be followed by its weight after a tab or a comma, like
+/// `early\t1200` or `early,1200`, and a first line whose weight is not a number
+/// is skipped as a header of CSV.
+pub struct TxtDictionary {
+    words: WordIndex,
+    guesses: WordIndex,
+    weights: Weights,
+}
+
+impl TxtDictionary {
+    /// Loads the dictionary at `path`, or the default dictionary if `path` is empty.
+    ///
+    /// The default dictionary is never downloaded, so use `TxtLoader` for it.
+    pub fn new(path: &str) -> Result<Self, Error> {
+        match path.is_empty() {
+            true => TxtLoader::new().load(),
+            false => TxtLoader::new().with_path(path).load(),
+        }
+    }
+
+    pub fn with_guesses(mut self, path: &str) -> Result<Self, Error> {
+        self.guesses = Self::index(Path::new(path))?.0;
+        Ok(self)
     }
 
     #[allow(dead_code)]
     fn new_for_debug(path: &str) -> Self {
-        Self {
-            file: File::open(path).unwrap()
+        Self::open(Path::new(path)).unwrap()
+    }
+
+    /// Lengths of words in the dictionary of answers, in ascending order.
+    pub fn word_lengths(&self) -> Vec<usize> {
+        let mut lengths: Vec<usize> = self.words.keys().copied().collect();
+        lengths.sort_unstable();
+        lengths
+    }
+
+    fn open(path: &Path) -> Result<Self, Error> {
+        let (words, weights) = Self::index(path)?;
+        Ok(TxtDictionary { words, guesses: WordIndex::new(), weights })
+    }
+
+    fn index(path: &Path) -> Result<(WordIndex, Weights), Error> {
+        let file = File::open(path).map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => Error::NotFound(path.to_path_buf()),
+            _ => Error::Io(e),
+        })?;
+        Self::read_index(io::BufReader::new(file), path)
+    }
+
+    fn read_index(reader: impl BufRead, path: &Path) -> Result<(WordIndex, Weights), Error> {
+        let mut index = WordIndex::new();
+        let mut weights = Weights::new();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| match e.kind() {
+                io::ErrorKind::InvalidData => Error::InvalidLine { path: path.to_path_buf(), line: i + 1 },
+                _ => Error::Io(e),
+            })?;
+            let (word, weight) = match line.split_once(['\t', ',']) {
+                Some((word, weight)) => (word.trim(), Some(weight.trim())),
+                None => (line.trim(), None),
+            };
+            if word.is_empty() {
+                continue;
+            }
+            if let Some(weight) = weight {
+                match weight.parse::<f64>() {
+                    Ok(weight) if weight.is_finite() && weight >= 0.0 => {
+                        weights.insert(word.to_string(), weight);
+                    }
+                    Err(_) if i == 0 => continue,
+                    _ => return Err(Error::InvalidWeight { path: path.to_path_buf(), line: i + 1 }),
+                }
+            }
+            index.entry(word.chars().count()).or_default().push(word.to_string());
         }
+        Ok((index, weights))
+    }
+
+    fn lookup(index: &WordIndex, word_length: usize) -> Vec<String> {
+        index.get(&word_length).cloned().unwrap_or_default()
     }
 }
 
 impl Dictionary for TxtDictionary {
-    fn extract_words(&self, word_length: usize) -> Vec<String> {
-        let dict: Vec<String> = io::BufReader::new(&self.file)
-            .lines()
-            .filter_map(|e| {
-                e.ok()
-            })
-            .filter(|w| {
-                w.chars().count() == word_length
+    fn extract_words(&self, word_length: usize) -> Result<Vec<String>, Error> {
+        let words = Self::lookup(&self.words, word_length);
+        match words.is_empty() {
+            true => Err(Error::Empty { word_length }),
+            false => Ok(words),
+        }
+    }
+
+    fn extract_guesses(&self, word_length: usize) -> Result<Vec<String>, Error> {
+        Ok(Self::lookup(&self.guesses, word_length))
+    }
+
+    fn extract_weights(&self, word_length: usize) -> Result<Weights, Error> {
+        Ok(self.weights.iter()
+            .filter(|(word, _)| {
+                word.chars().count() == word_length
             })
-            .map(|line| {
-                String::from(line.trim())
+            .map(|(word, weight)| {
+                (word.to_string(), *weight)
             })
-            .collect();
-        dict
+            .collect())
     }
 }
 
@@ -120,7 +302,7 @@ mod tests {
     #[test]
     fn extract_words_english() {
         let target = TxtDictionary::new_for_debug("src/testdata/english.txt");
-        let actual = target.extract_words(5);
+        let actual = target.extract_words(5).unwrap();
         assert_eq!(actual, vec![
             String::from("apple"),
             String::from("early"),
@@ -131,11 +313,160 @@ mod tests {
     #[test]
     fn extract_words_japanese() {
         let target = TxtDictionary::new_for_debug("src/testdata/japanese_pokemon.txt");
-        let actual = target.extract_words(5);
+        let actual = target.extract_words(5).unwrap();
         assert_eq!(actual, vec![
             String::from("フシギダネ"),
             String::from("サンドパン"),
             String::from("ニャスパー"),
         ])
     }
+
+    #[test]
+    fn extract_guesses_english() {
+        let target = TxtDictionary::new_for_debug("src/testdata/english.txt")
+            .with_guesses("src/testdata/english_guesses.txt")
+            .unwrap();
+        assert_eq!(target.extract_guesses(5).unwrap(), vec![
+            String::from("aalii"),
+            String::from("heard"),
+            String::from("tares"),
+        ]);
+        assert_eq!(target.extract_words(5).unwrap(), vec![
+            String::from("apple"),
+            String::from("early"),
+            String::from("asset"),
+        ]);
+    }
+
+    #[test]
+    fn extract_words_repeatedly() {
+        let target = TxtDictionary::new_for_debug("src/testdata/english.txt");
+        assert_eq!(target.extract_words(5).unwrap(), target.extract_words(5).unwrap());
+        assert_eq!(target.extract_words(5).unwrap().len(), 3);
+        assert!(target.word_lengths().contains(&5));
+    }
+
+    #[test]
+    fn extract_guesses_none() {
+        let target = TxtDictionary::new_for_debug("src/testdata/english.txt");
+        assert!(target.extract_guesses(5).unwrap().is_empty());
+    }
+
+    #[cfg(test)]
+    mod weights {
+        use super::*;
+
+        #[test]
+        fn tab_separated() {
+            let target = TxtDictionary::new_for_debug("src/testdata/english_weights.tsv");
+            assert_eq!(target.extract_words(5).unwrap(), vec![
+                String::from("apple"),
+                String::from("early"),
+                String::from("asset"),
+            ]);
+            let weights = target.extract_weights(5).unwrap();
+            assert_eq!(weights.len(), 2);
+            assert_eq!(weights["early"], 300.0);
+            assert!(!weights.contains_key("asset"));
+        }
+
+        #[test]
+        fn csv_with_header() {
+            let target = TxtDictionary::new_for_debug("src/testdata/english_weights.csv");
+            assert_eq!(target.extract_words(5).unwrap().len(), 3);
+            assert_eq!(target.extract_weights(5).unwrap()["asset"], 80.0);
+            assert_eq!(target.extract_weights(6).unwrap()["banana"], 5.0);
+        }
+
+        #[test]
+        fn without_weights() {
+            let target = TxtDictionary::new_for_debug("src/testdata/english.txt");
+            assert!(target.extract_weights(5).unwrap().is_empty());
+        }
+    }
+
+    #[cfg(test)]
+    mod loader {
+        use super::*;
+
+        #[test]
+        fn with_path_and_guesses() {
+            let target = TxtLoader::new()
+                .with_path("src/testdata/english.txt")
+                .with_guesses("src/testdata/english_guesses.txt")
+                .load()
+                .unwrap();
+            assert_eq!(target.extract_words(5).unwrap().len(), 3);
+            assert_eq!(target.extract_guesses(5).unwrap().len(), 3);
+        }
+
+        #[test]
+        fn never_asks_with_path() {
+            let target = TxtLoader::new()
+                .with_path("src/testdata/english.txt")
+                .with_download(DownloadPolicy::Ask(Box::new(|_| panic!("asked to download"))))
+                .with_progress(|_| panic!("downloading"))
+                .load();
+            assert!(target.is_ok());
+        }
+
+        #[test]
+        fn missing_guesses() {
+            let target = TxtLoader::new()
+                .with_path("src/testdata/english.txt")
+                .with_guesses("src/testdata/missing.txt")
+                .load();
+            assert!(matches!(target, Err(Error::NotFound(_))));
+        }
+    }
+
+    #[cfg(feature = "common-words")]
+    #[test]
+    fn common_words() {
+        let target = super::common_words().unwrap().unwrap();
+        let answers = target.extract_words(5).unwrap();
+        let guesses = target.extract_guesses(5).unwrap();
+        assert!(answers.contains(&String::from("early")));
+        assert!(!guesses.is_empty());
+        assert!(guesses.iter().all(|word| !answers.contains(word)));
+        assert_eq!(target.word_lengths(), vec![5]);
+    }
+
+    #[cfg(test)]
+    mod errors {
+        use super::*;
+
+        #[test]
+        fn not_found() {
+            match TxtDictionary::new("src/testdata/missing.txt") {
+                Err(Error::NotFound(path)) => assert_eq!(path, Path::new("src/testdata/missing.txt")),
+                other => panic!("unexpected result: {:?}", other.err()),
+            }
+        }
+
+        #[test]
+        fn invalid_line() {
+            match TxtDictionary::new("src/testdata/invalid_utf8.txt") {
+                Err(Error::InvalidLine { line, .. }) => assert_eq!(line, 2),
+                other => panic!("unexpected result: {:?}", other.err()),
+            }
+        }
+
+        #[test]
+        fn invalid_weight() {
+            match TxtDictionary::new("src/testdata/invalid_weight.tsv") {
+                Err(Error::InvalidWeight { line, .. }) => assert_eq!(line, 2),
+                other => panic!("unexpected result: {:?}", other.err()),
+            }
+        }
+
+        #[test]
+        fn empty() {
+            let target = TxtDictionary::new_for_debug("src/testdata/english.txt");
+            match target.extract_words(42) {
+                Err(Error::Empty { word_length }) => assert_eq!(word_length, 42),
+                other => panic!("unexpected result: {:?}", other),
+            }
+        }
+    }
 }
\ No newline at end of file